@@ -0,0 +1,187 @@
+use aoc_common::{ParseError, Part, Solution};
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// Each phase with how long it took on every run.
+pub type Timings = Vec<(Phase, Vec<Duration>)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+// Times parsing and each of `parts` separately, `runs` times over. Every run
+// parses the input afresh so that the phases are measured the same way the
+// runner executes them.
+pub fn phases<S: Solution>(
+    input: &str,
+    parts: &[Part],
+    runs: usize,
+) -> Result<Timings, ParseError> {
+    let mut timings: Timings = Vec::new();
+    timings.push((Phase::Parse, Vec::with_capacity(runs)));
+    for &part in parts {
+        timings.push((Phase::Solve(part), Vec::with_capacity(runs)));
+    }
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(input))?;
+        timings[0].1.push(start.elapsed());
+        for (i, &part) in parts.iter().enumerate() {
+            let start = Instant::now();
+            match part {
+                Part::One => {
+                    black_box(S::part_one(&parsed));
+                }
+                Part::Two => {
+                    black_box(S::part_two(&parsed));
+                }
+            }
+            timings[i + 1].1.push(start.elapsed());
+        }
+    }
+    Ok(timings)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Summary {
+    pub fn of(timings: &[Duration]) -> Summary {
+        let mut sorted = timings.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+        if runs == 0 {
+            return Summary {
+                runs,
+                min: Duration::ZERO,
+                median: Duration::ZERO,
+                mean: Duration::ZERO,
+                max: Duration::ZERO,
+            };
+        }
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+        Summary {
+            runs,
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / runs as u32,
+            max: sorted[runs - 1],
+        }
+    }
+}
+
+pub struct Row {
+    pub day: u8,
+    pub name: &'static str,
+    pub phase: Phase,
+    pub summary: Summary,
+}
+
+pub fn table(rows: &[Row]) -> String {
+    let mut table = format!(
+        "{:>3}  {:<8}{:>6}{:>12}{:>12}{:>12}{:>12}\n",
+        "day", "phase", "runs", "min", "median", "mean", "max"
+    );
+    for row in rows {
+        let s = &row.summary;
+        table += &format!(
+            "{:>3}  {:<8}{:>6}{:>12}{:>12}{:>12}{:>12}\n",
+            row.day,
+            row.phase.to_string(),
+            s.runs,
+            format!("{:.1?}", s.min),
+            format!("{:.1?}", s.median),
+            format!("{:.1?}", s.mean),
+            format!("{:.1?}", s.max)
+        );
+    }
+    table
+}
+
+// One JSON object per line, with times in nanoseconds, so results from
+// different revisions can be diffed or loaded into anything that reads JSON.
+pub fn json(rows: &[Row]) -> String {
+    let mut json = String::new();
+    for row in rows {
+        let s = &row.summary;
+        json += &format!(
+            "{{\"day\":{},\"name\":\"{}\",\"phase\":\"{}\",\"runs\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"max_ns\":{}}}\n",
+            row.day,
+            row.name,
+            row.phase,
+            s.runs,
+            s.min.as_nanos(),
+            s.median.as_nanos(),
+            s.mean.as_nanos(),
+            s.max.as_nanos()
+        );
+    }
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Summary::of(&millis(&[4, 1, 3, 8]));
+        assert_eq!(summary.runs, 4);
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(
+            summary.median,
+            Duration::from_millis(3) + Duration::from_micros(500)
+        );
+        assert_eq!(summary.mean, Duration::from_millis(4));
+        assert_eq!(summary.max, Duration::from_millis(8));
+        assert_eq!(
+            Summary::of(&millis(&[5, 2, 9])).median,
+            Duration::from_millis(5)
+        );
+    }
+
+    #[test]
+    fn test_phases() {
+        let timings = phases::<day_one::DayOne>("3   4\n4   3", &[Part::Two], 3).unwrap();
+        let phases: Vec<Phase> = timings.iter().map(|(phase, _)| *phase).collect();
+        assert_eq!(phases, [Phase::Parse, Phase::Solve(Part::Two)]);
+        assert!(timings.iter().all(|(_, runs)| runs.len() == 3));
+
+        let row = Row {
+            day: 1,
+            name: "day_one",
+            phase: Phase::Solve(Part::Two),
+            summary: Summary::of(&millis(&[2])),
+        };
+        assert_eq!(
+            json(&[row]),
+            "{\"day\":1,\"name\":\"day_one\",\"phase\":\"part 2\",\"runs\":1,\"min_ns\":2000000,\"median_ns\":2000000,\"mean_ns\":2000000,\"max_ns\":2000000}\n"
+        );
+    }
+}
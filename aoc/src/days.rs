@@ -0,0 +1,179 @@
+use crate::bench::{self, Timings};
+use aoc_common::input::Combined;
+use aoc_common::{Answer, InputSource, LoadError, ParseError, Part, Settings, Solution};
+use std::path::{Path, PathBuf};
+
+pub struct Day {
+    pub number: u8,
+    pub name: &'static str,
+    // Files (relative to the day's crate) that make up the puzzle input. Days
+    // that keep their input split across several files get them joined with a
+    // blank line, which is the puzzle's native layout.
+    pub inputs: &'static [&'static str],
+    pub validate: fn(&str, &Settings) -> Result<(), ParseError>,
+    pub solve: fn(&str, &Settings, Part) -> Result<Answer, ParseError>,
+    pub bench: fn(&str, &[Part], usize) -> Result<Timings, ParseError>,
+}
+
+pub fn workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+impl Day {
+    pub fn dir(&self) -> PathBuf {
+        workspace().join(self.name)
+    }
+
+    pub fn default_input(&self) -> Result<Combined, LoadError> {
+        let paths: Vec<PathBuf> = self
+            .inputs
+            .iter()
+            .map(|file| self.dir().join(file))
+            .collect();
+        let sources: Vec<&dyn InputSource> = paths.iter().map(|path| path as _).collect();
+        Combined::read(&sources)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        name: "day_one",
+        inputs: &["input.txt"],
+        validate: day_one::DayOne::validate,
+        solve: day_one::DayOne::solve,
+        bench: bench::phases::<day_one::DayOne>,
+    },
+    Day {
+        number: 2,
+        name: "day_two",
+        inputs: &["input.txt"],
+        validate: day_two::DayTwo::validate,
+        solve: day_two::DayTwo::solve,
+        bench: bench::phases::<day_two::DayTwo>,
+    },
+    Day {
+        number: 5,
+        name: "day_five",
+        inputs: &["rules.txt", "books.txt"],
+        validate: day_five::DayFive::validate,
+        solve: day_five::DayFive::solve,
+        bench: bench::phases::<day_five::DayFive>,
+    },
+    Day {
+        number: 6,
+        name: "day_six",
+        inputs: &["input.txt"],
+        validate: day_six::DaySix::validate,
+        solve: day_six::DaySix::solve,
+        bench: bench::phases::<day_six::DaySix>,
+    },
+    Day {
+        number: 7,
+        name: "day_seven",
+        inputs: &["input.txt"],
+        validate: day_seven::DaySeven::validate,
+        solve: day_seven::DaySeven::solve,
+        bench: bench::phases::<day_seven::DaySeven>,
+    },
+    Day {
+        number: 8,
+        name: "day_eight",
+        inputs: &["input.txt"],
+        validate: day_eight::DayEight::validate,
+        solve: day_eight::DayEight::solve,
+        bench: bench::phases::<day_eight::DayEight>,
+    },
+    Day {
+        number: 9,
+        name: "day_nine",
+        inputs: &["input.txt"],
+        validate: day_nine::DayNine::validate,
+        solve: day_nine::DayNine::solve,
+        bench: bench::phases::<day_nine::DayNine>,
+    },
+    Day {
+        number: 10,
+        name: "day_ten",
+        inputs: &["input.txt"],
+        validate: day_ten::DayTen::validate,
+        solve: day_ten::DayTen::solve,
+        bench: bench::phases::<day_ten::DayTen>,
+    },
+    Day {
+        number: 11,
+        name: "day_eleven",
+        inputs: &["input.txt"],
+        validate: day_eleven::DayEleven::validate,
+        solve: day_eleven::DayEleven::solve,
+        bench: bench::phases::<day_eleven::DayEleven>,
+    },
+    Day {
+        number: 12,
+        name: "day_twelve",
+        inputs: &["input.txt"],
+        validate: day_twelve::DayTwelve::validate,
+        solve: day_twelve::DayTwelve::solve,
+        bench: bench::phases::<day_twelve::DayTwelve>,
+    },
+    Day {
+        number: 13,
+        name: "day_thirteen",
+        inputs: &["input.txt"],
+        validate: day_thirteen::DayThirteen::validate,
+        solve: day_thirteen::DayThirteen::solve,
+        bench: bench::phases::<day_thirteen::DayThirteen>,
+    },
+    Day {
+        number: 14,
+        name: "day_fourteen",
+        inputs: &["input.txt"],
+        validate: day_fourteen::DayFourteen::validate,
+        solve: day_fourteen::DayFourteen::solve,
+        bench: bench::phases::<day_fourteen::DayFourteen>,
+    },
+    Day {
+        number: 15,
+        name: "day_fifteen",
+        inputs: &["map.txt", "input.txt"],
+        validate: day_fifteen::DayFifteen::validate,
+        solve: day_fifteen::DayFifteen::solve,
+        bench: bench::phases::<day_fifteen::DayFifteen>,
+    },
+    Day {
+        number: 16,
+        name: "day_sixteen",
+        inputs: &["input.txt"],
+        validate: day_sixteen::DaySixteen::validate,
+        solve: day_sixteen::DaySixteen::solve,
+        bench: bench::phases::<day_sixteen::DaySixteen>,
+    },
+    Day {
+        number: 17,
+        name: "day_seventeen",
+        inputs: &["input.txt"],
+        validate: day_seventeen::DaySeventeen::validate,
+        solve: day_seventeen::DaySeventeen::solve,
+        bench: bench::phases::<day_seventeen::DaySeventeen>,
+    },
+    Day {
+        number: 18,
+        name: "day_eighteen",
+        inputs: &["input.txt"],
+        validate: day_eighteen::DayEighteen::validate,
+        solve: day_eighteen::DayEighteen::solve,
+        bench: bench::phases::<day_eighteen::DayEighteen>,
+    },
+    Day {
+        number: 19,
+        name: "day_nineteen",
+        inputs: &["towels.txt", "input.txt"],
+        validate: day_nineteen::DayNineteen::validate,
+        solve: day_nineteen::DayNineteen::solve,
+        bench: bench::phases::<day_nineteen::DayNineteen>,
+    },
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,168 @@
+use crate::days::Day;
+use aoc_common::input::Combined;
+use aoc_common::{InputSource, Part, Settings};
+use std::fs::{read_dir, read_to_string};
+use std::io;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+const EXPECTED: &str = "expected.toml";
+
+// A puzzle example and its known answers. Examples live in the day's crate as
+// `example_N.txt`, with the answers for each under `[example_N]` in
+// `expected.toml`. An entry may list `inputs` to use other files, which get
+// joined the same way as the day's own input files, and `settings` for days
+// whose examples are smaller than the puzzle in ways the input doesn't show.
+pub struct Example {
+    pub name: String,
+    pub inputs: Vec<PathBuf>,
+    pub settings: Settings,
+    pub answers: Vec<(Part, String)>,
+}
+
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, found: String },
+    Error(String),
+}
+
+pub struct Check {
+    pub example: String,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+pub fn discover(day: &Day) -> Result<Vec<Example>, String> {
+    let dir = day.dir();
+    let expected = match read_to_string(dir.join(EXPECTED)) {
+        Ok(expected) => expected
+            .parse::<Table>()
+            .map_err(|e| format!("{}/{}: {}", day.name, EXPECTED, e))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Table::new(),
+        Err(e) => return Err(format!("could not read {}/{}: {}", day.name, EXPECTED, e)),
+    };
+
+    let mut names: Vec<String> = expected.keys().cloned().collect();
+    let files = read_dir(&dir).map_err(|e| format!("could not read {}: {}", day.name, e))?;
+    for entry in files.flatten() {
+        let file = entry.file_name().to_string_lossy().into_owned();
+        if let Some(name) = file.strip_suffix(".txt") {
+            if name.starts_with("example_") && !expected.contains_key(name) {
+                return Err(format!(
+                    "{}/{} has no entry for {}",
+                    day.name, EXPECTED, file
+                ));
+            }
+        }
+    }
+    names.sort_by_key(|name| (name.len(), name.clone()));
+
+    names
+        .into_iter()
+        .map(|name| {
+            let entry = expected[&name]
+                .as_table()
+                .ok_or_else(|| format!("{}/{}: {} is not a table", day.name, EXPECTED, name))?;
+            example(day, name, entry)
+        })
+        .collect()
+}
+
+fn example(day: &Day, name: String, entry: &Table) -> Result<Example, String> {
+    let invalid = |key: &str| format!("{}/{}: invalid {}.{}", day.name, EXPECTED, name, key);
+    let inputs = match entry.get("inputs") {
+        None => vec![day.dir().join(format!("{}.txt", name))],
+        Some(Value::Array(files)) => files
+            .iter()
+            .map(|file| file.as_str().map(|file| day.dir().join(file)))
+            .collect::<Option<_>>()
+            .ok_or_else(|| invalid("inputs"))?,
+        Some(_) => return Err(invalid("inputs")),
+    };
+    let mut settings = Settings::default();
+    match entry.get("settings") {
+        None => {}
+        Some(Value::Table(table)) => {
+            for (key, value) in table {
+                let value = value
+                    .as_integer()
+                    .and_then(|value| usize::try_from(value).ok())
+                    .ok_or_else(|| invalid(&format!("settings.{}", key)))?;
+                settings = settings.with(key, value);
+            }
+        }
+        Some(_) => return Err(invalid("settings")),
+    }
+    let mut answers = Vec::new();
+    for (part, key) in [(Part::One, "part_one"), (Part::Two, "part_two")] {
+        match entry.get(key) {
+            None => {}
+            Some(Value::Integer(answer)) => answers.push((part, answer.to_string())),
+            Some(Value::String(answer)) => answers.push((part, answer.clone())),
+            Some(_) => return Err(invalid(key)),
+        }
+    }
+    Ok(Example {
+        name,
+        inputs,
+        settings,
+        answers,
+    })
+}
+
+// Parses every example, even those without answers, and checks each answer.
+pub fn check(day: &Day) -> Result<Vec<Check>, String> {
+    let mut checks = Vec::new();
+    for example in discover(day)? {
+        let sources: Vec<&dyn InputSource> = example.inputs.iter().map(|path| path as _).collect();
+        let input = Combined::read(&sources).map_err(|e| e.to_string())?;
+        if let Err(e) = (day.validate)(&input.text, &example.settings) {
+            return Err(input.locate(e).to_string());
+        }
+        for (part, expected) in example.answers {
+            let outcome = match (day.solve)(&input.text, &example.settings, part) {
+                Ok(answer) if answer.to_string() == expected => Outcome::Pass,
+                Ok(answer) => Outcome::Fail {
+                    expected,
+                    found: answer.to_string(),
+                },
+                Err(e) => Outcome::Error(input.locate(e).to_string()),
+            };
+            checks.push(Check {
+                example: example.name.clone(),
+                part,
+                outcome,
+            });
+        }
+    }
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::DAYS;
+
+    #[test]
+    fn test_every_example() {
+        let mut failures = Vec::new();
+        for day in DAYS {
+            match check(day) {
+                Ok(checks) => {
+                    for check in checks {
+                        match check.outcome {
+                            Outcome::Pass => {}
+                            Outcome::Fail { expected, found } => failures.push(format!(
+                                "{} {} part {}: expected {}, found {}",
+                                day.name, check.example, check.part, expected, found
+                            )),
+                            Outcome::Error(e) => failures.push(e),
+                        }
+                    }
+                }
+                Err(e) => failures.push(e),
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
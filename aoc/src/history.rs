@@ -0,0 +1,242 @@
+use aoc_common::{input, parse, LoadError, ParseError, Part};
+use std::fs::{create_dir_all, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const FIELDS: &str = "day, part, input hash, answer, microseconds, revision and time";
+
+// One answer the runner produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub input_hash: u64,
+    pub answer: String,
+    pub elapsed: Duration,
+    pub revision: String,
+    // Seconds since the Unix epoch.
+    pub recorded_at: u64,
+}
+
+impl Record {
+    fn parse(line: &parse::Line) -> Result<Record, ParseError> {
+        let fields: Vec<&str> = line.text.split('\t').collect();
+        let &[day, part, input_hash, answer, elapsed, revision, recorded_at] = &fields[..] else {
+            return Err(line.error(line.text, FIELDS));
+        };
+        Ok(Record {
+            day: line.parse(day, "a day number")?,
+            part: line.parse(part, "a part")?,
+            input_hash: u64::from_str_radix(input_hash, 16)
+                .map_err(|_| line.error(input_hash, "a hexadecimal hash"))?,
+            answer: unescape(answer).ok_or_else(|| line.error(answer, "an answer"))?,
+            elapsed: Duration::from_micros(line.parse(elapsed, "a number of microseconds")?),
+            revision: revision.to_string(),
+            recorded_at: line.parse(recorded_at, "a timestamp")?,
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{:016x}\t{}\t{}\t{}\t{}",
+            self.day,
+            self.part,
+            self.input_hash,
+            escape(&self.answer),
+            self.elapsed.as_micros(),
+            self.revision,
+            self.recorded_at
+        )
+    }
+}
+
+// Answers are free text, so backslashes, tabs and line breaks in them are
+// written as escapes to keep each record on one line.
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        unescaped.push(match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            _ => c,
+        });
+    }
+    Some(unescaped)
+}
+
+// Every answer recorded so far, kept as an append-only file with a record per
+// line.
+pub struct History {
+    path: PathBuf,
+    records: Vec<Record>,
+}
+
+impl History {
+    pub fn default_path() -> PathBuf {
+        crate::days::workspace().join(".aoc").join("history.tsv")
+    }
+
+    pub fn open(path: impl Into<PathBuf>) -> Result<History, LoadError> {
+        let path = path.into();
+        let records = if path.exists() {
+            input::load(path.as_path(), |contents| {
+                parse::lines(contents)
+                    .filter(|line| !line.text.is_empty())
+                    .map(|line| Record::parse(&line))
+                    .collect()
+            })?
+        } else {
+            Vec::new()
+        };
+        Ok(History { path, records })
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    // The most recent answer for this part of a day given the same input.
+    pub fn previous(&self, day: u8, part: Part, input_hash: u64) -> Option<&Record> {
+        self.records
+            .iter()
+            .rev()
+            .find(|r| r.day == day && r.part == part && r.input_hash == input_hash)
+    }
+
+    pub fn record(&mut self, record: Record) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", record.to_line())?;
+        self.records.push(record);
+        Ok(())
+    }
+}
+
+// FNV-1a, which unlike std's hasher is stable between builds.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// Short commit hash of the workspace, marked dirty if there are uncommitted
+// changes, or "unknown" outside a git checkout.
+pub fn revision() -> String {
+    let workspace = crate::days::workspace();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(&workspace)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{}-dirty", commit)
+        }
+        Some(commit) => commit,
+        None => "unknown".to_string(),
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{read_to_string, remove_file};
+
+    fn record(answer: &str) -> Record {
+        Record {
+            day: 9,
+            part: Part::Two,
+            input_hash: hash("2333133121414131402"),
+            answer: answer.to_string(),
+            elapsed: Duration::from_micros(1500),
+            revision: "abc1234".to_string(),
+            recorded_at: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_hash_is_stable() {
+        assert_eq!(hash(""), 0xcbf29ce484222325);
+        assert_eq!(hash("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_records_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.tsv", std::process::id()));
+        let mut history = History::open(&path).unwrap();
+        assert!(history.records().is_empty());
+        history.record(record("2858")).unwrap();
+        history.record(record("2859")).unwrap();
+
+        let reopened = History::open(&path).unwrap();
+        assert_eq!(reopened.records(), &[record("2858"), record("2859")]);
+        let previous = reopened.previous(9, Part::Two, record("").input_hash);
+        assert_eq!(previous.unwrap().answer, "2859");
+        assert!(reopened
+            .previous(9, Part::One, record("").input_hash)
+            .is_none());
+
+        let contents = read_to_string(&path).unwrap();
+        remove_file(&path).unwrap();
+        assert!(contents.starts_with("9\t2\t"));
+    }
+
+    #[test]
+    fn test_answers_with_separators_round_trip() {
+        for answer in ["22,33", "a\tb", "two\nlines\r\n", "back\\slash\\t", ""] {
+            let line = record(answer).to_line();
+            assert_eq!(line.split('\t').count(), 7, "{:?}", line);
+            assert!(!line.contains('\n'), "{:?}", line);
+            let parsed = Record::parse(&parse::lines(&line).next().unwrap());
+            assert_eq!(parsed, Ok(record(answer)));
+        }
+    }
+
+    #[test]
+    fn test_malformed_record() {
+        let error = Record::parse(&parse::lines("9\t3\tff\t1\t1\tabc\t1").next().unwrap());
+        assert_eq!(error.unwrap_err(), ParseError::new(1, 3, "a part", "\"3\""));
+        let error = Record::parse(&parse::lines("9\t2\tff\t1\\x\t1\tabc\t1").next().unwrap());
+        assert_eq!(
+            error.unwrap_err(),
+            ParseError::new(1, 8, "an answer", "\"1\\\\x\"")
+        );
+    }
+}
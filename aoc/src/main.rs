@@ -0,0 +1,291 @@
+mod bench;
+mod days;
+mod examples;
+mod history;
+
+use aoc_common::input::Combined;
+use aoc_common::{Answer, Part, Settings};
+use clap::{Parser, Subcommand};
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Run Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one or both parts of a day
+    Run {
+        day: u8,
+        #[arg(short, long)]
+        part: Option<Part>,
+        /// Puzzle input, `-` for stdin; defaults to the input files in the day's crate
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Don't add the answers to the history
+        #[arg(long)]
+        no_record: bool,
+    },
+    /// Show the answers recorded by earlier runs
+    History {
+        /// Only show this day
+        day: Option<u8>,
+    },
+    /// Time parsing and each part separately on the default inputs
+    Bench {
+        /// Only benchmark this day
+        day: Option<u8>,
+        #[arg(short, long)]
+        part: Option<Part>,
+        /// How many times to run each day
+        #[arg(short, long, default_value_t = 10)]
+        runs: usize,
+        /// Print one JSON object per line instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the puzzle examples against their expected answers
+    Test {
+        /// Only check this day
+        day: Option<u8>,
+    },
+    /// List the days that can be run
+    List,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            no_record,
+        } => {
+            let Some(day) = days::find(day) else {
+                eprintln!("Day {} is not available, see `aoc list`", day);
+                return ExitCode::FAILURE;
+            };
+            let input = match input {
+                Some(path) if path.as_os_str() == "-" => Combined::read(&[&io::stdin()]),
+                Some(path) => Combined::read(&[&path]),
+                None => day.default_input(),
+            };
+            let input = match input {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Day {}: {}", day.number, e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let parts = match part {
+                Some(part) => vec![part],
+                None => Part::ALL.to_vec(),
+            };
+            let mut history = if no_record {
+                None
+            } else {
+                match history::History::open(history::History::default_path()) {
+                    Ok(history) => Some(history),
+                    Err(e) => {
+                        eprintln!("Not recording answers, the history is unreadable: {}", e);
+                        None
+                    }
+                }
+            };
+            let input_hash = history::hash(&input.text);
+            for part in parts {
+                let start = Instant::now();
+                let result = (day.solve)(&input.text, &Settings::default(), part);
+                let elapsed = start.elapsed();
+                match result {
+                    Ok(answer) => {
+                        println!("Day {} part {}: {}", day.number, part, answer);
+                        if let Some(history) = &mut history {
+                            record(history, day, part, input_hash, &answer, elapsed);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "Could not parse input for day {}: {}",
+                            day.number,
+                            input.locate(e)
+                        );
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+        }
+        Command::Bench {
+            day,
+            part,
+            runs,
+            json,
+        } => {
+            let days: Vec<&days::Day> = match day {
+                Some(number) => match days::find(number) {
+                    Some(day) => vec![day],
+                    None => {
+                        eprintln!("Day {} is not available, see `aoc list`", number);
+                        return ExitCode::FAILURE;
+                    }
+                },
+                None => days::DAYS.iter().collect(),
+            };
+            let parts = match part {
+                Some(part) => vec![part],
+                None => Part::ALL.to_vec(),
+            };
+            let rows = benchmark(&days, &parts, runs);
+            if json {
+                print!("{}", bench::json(&rows));
+            } else {
+                print!("{}", bench::table(&rows));
+            }
+        }
+        Command::Test { day } => {
+            let days: Vec<&days::Day> = match day {
+                Some(number) => match days::find(number) {
+                    Some(day) => vec![day],
+                    None => {
+                        eprintln!("Day {} is not available, see `aoc list`", number);
+                        return ExitCode::FAILURE;
+                    }
+                },
+                None => days::DAYS.iter().collect(),
+            };
+            if !test(&days) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::History { day } => {
+            let history = match history::History::open(history::History::default_path()) {
+                Ok(history) => history,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            for record in history.records() {
+                if day.is_some_and(|day| day != record.day) {
+                    continue;
+                }
+                println!(
+                    "Day {} part {}: {} ({:.1?}, input {:016x}, revision {}, at {})",
+                    record.day,
+                    record.part,
+                    record.answer,
+                    record.elapsed,
+                    record.input_hash,
+                    record.revision,
+                    record.recorded_at
+                );
+            }
+        }
+        Command::List => {
+            for day in days::DAYS {
+                println!("{:>2}  {}", day.number, day.name);
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+// Prints a line per checked answer and reports whether everything passed.
+fn test(days: &[&days::Day]) -> bool {
+    let mut passed = true;
+    for day in days {
+        let checks = match examples::check(day) {
+            Ok(checks) => checks,
+            Err(e) => {
+                println!("Day {}: {}", day.number, e);
+                passed = false;
+                continue;
+            }
+        };
+        for check in checks {
+            let result = match check.outcome {
+                examples::Outcome::Pass => "ok".to_string(),
+                examples::Outcome::Fail { expected, found } => {
+                    passed = false;
+                    format!("FAILED, expected {} but found {}", expected, found)
+                }
+                examples::Outcome::Error(e) => {
+                    passed = false;
+                    format!("FAILED, {}", e)
+                }
+            };
+            println!(
+                "Day {} {} part {}: {}",
+                day.number, check.example, check.part, result
+            );
+        }
+    }
+    passed
+}
+
+// Adds an answer to the history, warning if it differs from the last answer
+// recorded for the same input.
+fn record(
+    history: &mut history::History,
+    day: &days::Day,
+    part: Part,
+    input_hash: u64,
+    answer: &Answer,
+    elapsed: Duration,
+) {
+    if *answer == Answer::Unsolved {
+        return;
+    }
+    let record = history::Record {
+        day: day.number,
+        part,
+        input_hash,
+        answer: answer.to_string(),
+        elapsed,
+        revision: history::revision(),
+        recorded_at: history::now(),
+    };
+    if let Some(previous) = history.previous(day.number, part, input_hash) {
+        if previous.answer != record.answer {
+            eprintln!(
+                "Warning: day {} part {} changed, it gave {} for the same input at revision {}",
+                day.number, part, previous.answer, previous.revision
+            );
+        }
+    }
+    if let Err(e) = history.record(record) {
+        eprintln!("Could not record the answer: {}", e);
+    }
+}
+
+// Days without a default input, or whose input doesn't parse, are reported and
+// left out.
+fn benchmark(days: &[&days::Day], parts: &[Part], runs: usize) -> Vec<bench::Row> {
+    let mut rows = Vec::new();
+    for day in days {
+        let input = match day.default_input() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Skipping day {}: {}", day.number, e);
+                continue;
+            }
+        };
+        match (day.bench)(&input.text, parts, runs) {
+            Ok(timings) => rows.extend(timings.into_iter().map(|(phase, timings)| bench::Row {
+                day: day.number,
+                name: day.name,
+                phase,
+                summary: bench::Summary::of(&timings),
+            })),
+            Err(e) => eprintln!("Skipping day {}: {}", day.number, input.locate(e)),
+        }
+    }
+    rows
+}
@@ -0,0 +1,80 @@
+use crate::input::Combined;
+use crate::{InputSource, LoadError, ParseError, Part};
+use clap::{ArgAction, Parser};
+use std::io;
+use std::path::PathBuf;
+
+// Command line shared by the day binaries.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Puzzle input, `-` for stdin. Several inputs are joined with a blank line,
+    /// so split inputs can be given as separate files or as one. Defaults to the
+    /// day's input files in the current directory
+    #[arg(short, long = "input", value_name = "PATH")]
+    pub inputs: Vec<PathBuf>,
+    /// Only run this part
+    #[arg(short, long)]
+    pub part: Option<Part>,
+    /// Show the working as well as the answers, repeat for more
+    #[arg(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+}
+
+impl Args {
+    // So the day crates don't need clap themselves.
+    pub fn from_env() -> Args {
+        Args::parse()
+    }
+
+    pub fn wants(&self, part: Part) -> bool {
+        self.part.is_none_or(|wanted| wanted == part)
+    }
+
+    pub fn read(&self, defaults: &[&str]) -> Result<Combined, LoadError> {
+        let paths: Vec<PathBuf> = if self.inputs.is_empty() {
+            defaults.iter().map(PathBuf::from).collect()
+        } else {
+            self.inputs.clone()
+        };
+        let stdin = io::stdin();
+        let sources: Vec<&dyn InputSource> = paths
+            .iter()
+            .map(|path| -> &dyn InputSource {
+                if path.as_os_str() == "-" {
+                    &stdin
+                } else {
+                    path
+                }
+            })
+            .collect();
+        Combined::read(&sources)
+    }
+
+    pub fn load<T>(
+        &self,
+        defaults: &[&str],
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Result<T, LoadError> {
+        self.read(defaults)?.parse(parse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arguments() {
+        let args = Args::parse_from(["day", "-i", "a.txt", "--input", "b.txt", "-p", "2", "-vv"]);
+        assert_eq!(
+            args.inputs,
+            [PathBuf::from("a.txt"), PathBuf::from("b.txt")]
+        );
+        assert!(!args.wants(Part::One));
+        assert!(args.wants(Part::Two));
+        assert_eq!(args.verbose, 2);
+
+        let args = Args::parse_from(["day"]);
+        assert!(args.inputs.is_empty() && args.wants(Part::One) && args.wants(Part::Two));
+    }
+}
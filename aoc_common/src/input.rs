@@ -0,0 +1,280 @@
+use crate::ParseError;
+use std::error::Error;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io::{self, Read, Stdin};
+use std::path::{Path, PathBuf};
+
+// Somewhere puzzle input can be read from. Paths are read as files, while
+// strings are the input itself, so a file name has to be passed as a `Path`.
+pub trait InputSource {
+    // Used to label errors, e.g. the file name.
+    fn name(&self) -> String;
+    fn read(&self) -> io::Result<String>;
+}
+
+impl<T: InputSource + ?Sized> InputSource for &T {
+    fn name(&self) -> String {
+        (**self).name()
+    }
+
+    fn read(&self) -> io::Result<String> {
+        (**self).read()
+    }
+}
+
+impl InputSource for Path {
+    fn name(&self) -> String {
+        self.display().to_string()
+    }
+
+    fn read(&self) -> io::Result<String> {
+        read_to_string(self)
+    }
+}
+
+impl InputSource for PathBuf {
+    fn name(&self) -> String {
+        self.as_path().name()
+    }
+
+    fn read(&self) -> io::Result<String> {
+        self.as_path().read()
+    }
+}
+
+impl InputSource for Stdin {
+    fn name(&self) -> String {
+        "stdin".to_string()
+    }
+
+    fn read(&self) -> io::Result<String> {
+        let mut contents = String::new();
+        self.lock().read_to_string(&mut contents)?;
+        Ok(contents)
+    }
+}
+
+impl InputSource for str {
+    fn name(&self) -> String {
+        "input".to_string()
+    }
+
+    fn read(&self) -> io::Result<String> {
+        Ok(self.to_string())
+    }
+}
+
+impl InputSource for String {
+    fn name(&self) -> String {
+        self.as_str().name()
+    }
+
+    fn read(&self) -> io::Result<String> {
+        self.as_str().read()
+    }
+}
+
+impl InputSource for Vec<String> {
+    fn name(&self) -> String {
+        "input".to_string()
+    }
+
+    fn read(&self) -> io::Result<String> {
+        Ok(self.join("\n"))
+    }
+}
+
+// Input compiled into the binary, see `fixture!`.
+#[derive(Debug, Clone, Copy)]
+pub struct Fixture {
+    pub name: &'static str,
+    pub contents: &'static str,
+}
+
+impl InputSource for Fixture {
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn read(&self) -> io::Result<String> {
+        Ok(self.contents.to_string())
+    }
+}
+
+// Embeds a file, relative to the calling crate's root, as a `Fixture`.
+#[macro_export]
+macro_rules! fixture {
+    ($path:literal) => {
+        $crate::input::Fixture {
+            name: $path,
+            contents: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path)),
+        }
+    };
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io { name: String, error: io::Error },
+    Parse(ParseError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io { name, error } => write!(f, "could not read {}: {}", name, error),
+            LoadError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io { error, .. } => Some(error),
+            LoadError::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl From<ParseError> for LoadError {
+    fn from(error: ParseError) -> Self {
+        LoadError::Parse(error)
+    }
+}
+
+pub fn read(source: &(impl InputSource + ?Sized)) -> Result<String, LoadError> {
+    source.read().map_err(|error| LoadError::Io {
+        name: source.name(),
+        error,
+    })
+}
+
+// Reads and parses `source`, naming it in any parse error.
+pub fn load<T>(
+    source: impl InputSource,
+    parse: impl FnOnce(&str) -> Result<T, ParseError>,
+) -> Result<T, LoadError> {
+    let contents = read(&source)?;
+    parse(&contents).map_err(|e| LoadError::Parse(e.in_file(source.name())))
+}
+
+// Input read from several sources and joined with a blank line between them,
+// which is the puzzle's native layout for inputs kept in several files. It
+// remembers where each source starts so that parse errors can be traced back
+// to a file and a line within it.
+pub struct Combined {
+    pub text: String,
+    // Each source's name and the number of lines it contributed.
+    files: Vec<(String, usize)>,
+}
+
+impl Combined {
+    pub fn read(sources: &[&dyn InputSource]) -> Result<Combined, LoadError> {
+        let mut parts = Vec::new();
+        let mut files = Vec::new();
+        for source in sources {
+            let contents = read(*source)?;
+            let contents = contents.trim_end().to_string();
+            files.push((source.name(), contents.lines().count()));
+            parts.push(contents);
+        }
+        Ok(Combined {
+            text: parts.join("\n\n"),
+            files,
+        })
+    }
+
+    pub fn parse<T>(
+        &self,
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Result<T, LoadError> {
+        parse(&self.text).map_err(|e| LoadError::Parse(self.locate(e)))
+    }
+
+    pub fn locate(&self, error: ParseError) -> ParseError {
+        if error.line == 0 {
+            let names: Vec<&str> = self.files.iter().map(|(name, _)| name.as_str()).collect();
+            return error.in_file(names.join(", "));
+        }
+        let mut first_line = 1;
+        for (i, (name, lines)) in self.files.iter().enumerate() {
+            if error.line <= first_line + lines || i == self.files.len() - 1 {
+                return ParseError {
+                    line: error.line + 1 - first_line,
+                    ..error
+                }
+                .in_file(name.as_str());
+            }
+            first_line += lines + 1;
+        }
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn sum(input: &str) -> Result<u32, ParseError> {
+        parse::lines(input)
+            .map(|line| line.parse::<u32>(line.text, "a number"))
+            .sum()
+    }
+
+    #[test]
+    fn test_in_memory_sources() {
+        assert_eq!(load("1\n2", sum).unwrap(), 3);
+        assert_eq!(
+            load(vec!["1".to_string(), "5".to_string()], sum).unwrap(),
+            6
+        );
+        let fixture = Fixture {
+            name: "numbers.txt",
+            contents: "4\nfour\n",
+        };
+        assert_eq!(
+            load(fixture, sum).unwrap_err().to_string(),
+            "numbers.txt:2:1: expected a number, found \"four\""
+        );
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let error = load(Path::new("does/not/exist.txt"), sum).unwrap_err();
+        assert!(matches!(error, LoadError::Io { .. }));
+        assert!(error
+            .to_string()
+            .starts_with("could not read does/not/exist.txt"));
+    }
+
+    #[test]
+    fn test_combined_errors_name_the_source() {
+        let rules = Fixture {
+            name: "rules.txt",
+            contents: "1|2\n2|3\n",
+        };
+        let books = Fixture {
+            name: "books.txt",
+            contents: "1\nx\n",
+        };
+        let combined = Combined::read(&[&rules, &books]).unwrap();
+        assert_eq!(combined.text, "1|2\n2|3\n\n1\nx");
+        let error = combined.parse(sum).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "rules.txt:1:1: expected a number, found \"1|2\""
+        );
+        let error = combined.locate(ParseError::new(5, 1, "a page", "\"x\""));
+        assert_eq!(error.file.as_deref(), Some("books.txt"));
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn test_fixture_macro() {
+        let fixture = crate::fixture!("Cargo.toml");
+        assert_eq!(fixture.name(), "Cargo.toml");
+        assert!(fixture.read().unwrap().contains("name = \"aoc_common\""));
+    }
+}
@@ -0,0 +1,231 @@
+pub mod cli;
+pub mod input;
+pub mod parse;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+pub use input::{InputSource, LoadError};
+pub use parse::ParseError;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => Err(format!("Invalid part: {} (expected 1 or 2)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(n) => Answer::Number(n),
+                        Err(_) => Answer::Text(value.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, u32, u64, usize);
+
+impl From<Unsolved> for Answer {
+    fn from(_: Unsolved) -> Self {
+        Answer::Unsolved
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+// For the day binaries: reports what went wrong (usually a missing or malformed
+// input file) and exits instead of panicking with a backtrace.
+pub fn unwrap_or_exit<T, E: fmt::Display>(result: Result<T, E>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1)
+    })
+}
+
+// Result type for parts a day doesn't have a solution for yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+// Sizes and counts an input needs that it doesn't say itself, such as the
+// smaller grid a puzzle's example is played on. Examples give them under
+// `settings` in their `expected.toml` entry; the puzzle input has none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Settings(BTreeMap<String, usize>);
+
+impl Settings {
+    pub fn get(&self, key: &str) -> Option<usize> {
+        self.0.get(key).copied()
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: usize) -> Self {
+        self.0.insert(key.into(), value);
+        self
+    }
+}
+
+pub trait Solution {
+    type Input;
+    type PartOne: Into<Answer>;
+    type PartTwo: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    // For days whose inputs need `Settings`; the rest ignore them.
+    fn parse_with(input: &str, _settings: &Settings) -> Result<Self::Input, ParseError> {
+        Self::parse(input)
+    }
+    fn part_one(input: &Self::Input) -> Self::PartOne;
+    fn part_two(input: &Self::Input) -> Self::PartTwo;
+
+    fn load(source: impl InputSource) -> Result<Self::Input, LoadError>
+    where
+        Self: Sized,
+    {
+        input::load(source, Self::parse)
+    }
+
+    // Parses the input without solving anything.
+    fn validate(input: &str, settings: &Settings) -> Result<(), ParseError> {
+        Self::parse_with(input, settings).map(|_| ())
+    }
+
+    fn solve(input: &str, settings: &Settings, part: Part) -> Result<Answer, ParseError> {
+        let input = Self::parse_with(input, settings)?;
+        Ok(match part {
+            Part::One => Self::part_one(&input).into(),
+            Part::Two => Self::part_two(&input).into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("two".parse::<Part>(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_answer_conversions() {
+        assert_eq!(Answer::from(42usize), Answer::Number(42));
+        assert_eq!(Answer::from(u64::MAX), Answer::Text(u64::MAX.to_string()));
+        assert_eq!(Answer::from("6,4").to_string(), "6,4");
+        assert_eq!(Answer::from(None::<i32>), Answer::Unsolved);
+        assert_eq!(Answer::from(Some(7)), Answer::Number(7));
+    }
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+        type PartOne = i64;
+        type PartTwo = Unsolved;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            let mut numbers = Vec::new();
+            for line in parse::lines(input) {
+                for n in line.text.split_whitespace() {
+                    numbers.push(line.parse(n, "a number")?);
+                }
+            }
+            Ok(numbers)
+        }
+
+        fn part_one(input: &Self::Input) -> Self::PartOne {
+            input.iter().sum()
+        }
+
+        fn part_two(_: &Self::Input) -> Self::PartTwo {
+            Unsolved
+        }
+    }
+
+    #[test]
+    fn test_solve_dispatches_parts() {
+        assert_eq!(
+            Sum::solve("1 2 3", &Settings::default(), Part::One),
+            Ok(Answer::Number(6))
+        );
+        assert_eq!(
+            Sum::solve("1 2 3", &Settings::default(), Part::Two),
+            Ok(Answer::Unsolved)
+        );
+        assert_eq!(
+            Sum::solve("1 2\n3 x", &Settings::default(), Part::One),
+            Err(ParseError::new(2, 3, "a number", "\"x\""))
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// Where a puzzle input stopped making sense. Lines and columns are 1-based;
+// line 0 means the problem is with the input as a whole (a missing start
+// tile, say) rather than with any particular line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            file: None,
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    pub fn whole_input(expected: impl Into<String>, found: impl Into<String>) -> Self {
+        ParseError::new(0, 0, expected, found)
+    }
+
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file.get_or_insert_with(|| file.into());
+        self
+    }
+
+    // For inputs made of several sections, where the section that failed
+    // started `lines` lines into the input.
+    pub fn offset(mut self, lines: usize) -> Self {
+        if self.line > 0 {
+            self.line += lines;
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.file.as_deref().unwrap_or("input"))?;
+        if self.line > 0 {
+            write!(f, ":{}:{}", self.line, self.column)?;
+        }
+        write!(f, ": expected {}, found {}", self.expected, self.found)
+    }
+}
+
+impl Error for ParseError {}
+
+// Describes a piece of input for the `found` half of an error message.
+pub fn describe(found: &str) -> String {
+    if found.is_empty() {
+        "end of line".to_string()
+    } else {
+        format!("{:?}", found)
+    }
+}
+
+// Splits `input` at its first blank line, whatever its line endings, giving
+// the two sections and the number of lines before the second for use with
+// `ParseError::offset`. None if there's no blank line.
+pub fn split_sections(input: &str) -> Option<(&str, &str, usize)> {
+    let blank = lines(input).find(|line| line.text.trim().is_empty())?;
+    let start = blank.text.as_ptr() as usize - input.as_ptr() as usize;
+    let end = input[start..]
+        .find('\n')
+        .map_or(input.len(), |i| start + i + 1);
+    Some((&input[..start], &input[end..], blank.number))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+impl<'a> Line<'a> {
+    // Column of `part`, which should be a slice of this line's text. Anything
+    // else is reported against the start of the line.
+    pub fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        match self.text.get(..offset) {
+            Some(before) => before.chars().count() + 1,
+            None => 1,
+        }
+    }
+
+    pub fn error(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column_of(part), expected, describe(part))
+    }
+
+    pub fn parse<T: FromStr>(&self, part: &'a str, expected: &str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error(part, expected))
+    }
+
+    pub fn strip_prefix(&self, part: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        part.strip_prefix(prefix)
+            .ok_or_else(|| self.error(part, format!("{:?}", prefix)))
+    }
+
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        separator: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(separator)
+            .ok_or_else(|| self.error(part, format!("{:?}", separator)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_errors_point_at_the_bad_part() {
+        let line = lines("1|2\n3|x").nth(1).unwrap();
+        let (_, after) = line.split_once(line.text, "|").unwrap();
+        let error = line.parse::<u32>(after, "a page number").unwrap_err();
+        assert_eq!(error, ParseError::new(2, 3, "a page number", "\"x\""));
+        assert_eq!(
+            error.in_file("rules.txt").to_string(),
+            "rules.txt:2:3: expected a page number, found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_missing_separator() {
+        let line = lines("12").next().unwrap();
+        let error = line.split_once(line.text, "|").unwrap_err();
+        assert_eq!(error, ParseError::new(1, 1, "\"|\"", "\"12\""));
+        assert_eq!(error.offset(4).line, 5);
+        assert_eq!(line.error(&line.text[2..], "a digit").found, "end of line");
+    }
+
+    #[test]
+    fn test_split_sections() {
+        assert_eq!(split_sections("a\nb\n\nc\n"), Some(("a\nb\n", "c\n", 3)));
+        assert_eq!(
+            split_sections("a\r\nb\r\n\r\nc\r\n\r\n"),
+            Some(("a\r\nb\r\n", "c\r\n\r\n", 3))
+        );
+        assert_eq!(split_sections("a\n\n"), Some(("a\n", "", 2)));
+        assert_eq!(split_sections("a\nb\n"), None);
+    }
+
+    #[test]
+    fn test_whole_input_errors() {
+        let error = ParseError::whole_input("a guard", "none");
+        assert_eq!(
+            error.offset(3).to_string(),
+            "input: expected a guard, found none"
+        );
+    }
+}
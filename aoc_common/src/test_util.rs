@@ -0,0 +1,41 @@
+use std::fmt::Debug;
+
+// A small seeded generator, so tests on made-up inputs see the same inputs
+// every run.
+pub struct Random(u32);
+
+impl Random {
+    pub fn new(seed: u32) -> Self {
+        Random(seed)
+    }
+
+    // A number below `limit`.
+    pub fn below(&mut self, limit: u32) -> u32 {
+        self.0 = self.0.wrapping_mul(1103515245).wrapping_add(12345);
+        (self.0 >> 16) % limit
+    }
+
+    pub fn one_in(&mut self, n: u32) -> bool {
+        self.below(n) == 0
+    }
+}
+
+// Checks `actual` against a simpler `reference` on `cases` inputs from
+// `generate`, naming the input they disagree on. Returns what they agreed.
+pub fn compare<T: Debug, R: PartialEq + Debug>(
+    seed: u32,
+    cases: usize,
+    mut generate: impl FnMut(&mut Random) -> T,
+    actual: impl Fn(&T) -> R,
+    reference: impl Fn(&T) -> R,
+) -> Vec<R> {
+    let mut random = Random::new(seed);
+    (0..cases)
+        .map(|_| {
+            let input = generate(&mut random);
+            let result = actual(&input);
+            assert_eq!(result, reference(&input), "input: {:?}", input);
+            result
+        })
+        .collect()
+}
@@ -0,0 +1,171 @@
+use aoc_common::{parse, ParseError, Settings, Solution};
+use grid::{Grid, Point};
+use std::collections::VecDeque;
+
+pub struct MemorySpace {
+    corrupted: Grid<bool>,
+}
+
+impl MemorySpace {
+    pub fn new(height: usize, width: usize) -> Self {
+        MemorySpace {
+            corrupted: Grid::new(width, height, false),
+        }
+    }
+    pub fn set_coordinates(&mut self, coordinates: &[(usize, usize)]) {
+        for &coordinate in coordinates {
+            self.corrupted.set(Point::from(coordinate), true);
+        }
+    }
+    pub fn find_path(&self) -> Option<Vec<Point>> {
+        let start = Point::ORIGIN;
+        let end = Point::from((self.corrupted.width() - 1, self.corrupted.height() - 1));
+
+        let mut queue = VecDeque::new();
+        let mut visited = Grid::new(self.corrupted.width(), self.corrupted.height(), false);
+
+        queue.push_back((start, vec![start]));
+        visited[start] = true;
+
+        while let Some((position, path)) = queue.pop_front() {
+            if position == end {
+                return Some(path);
+            }
+            for next in self.corrupted.neighbours(position) {
+                if !visited[next] && !self.corrupted[next] {
+                    let mut new_path = path.clone();
+                    new_path.push(next);
+                    queue.push_back((next, new_path));
+                    visited[next] = true;
+                }
+            }
+        }
+        None
+    }
+}
+
+// How much of memory the puzzle looks at: the real puzzle is a 71x71 space
+// once 1024 bytes have fallen, the example a 7x7 one once 12 have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setup {
+    pub size: usize,
+    pub fallen: usize,
+}
+
+impl Setup {
+    pub const PUZZLE: Setup = Setup {
+        size: 71,
+        fallen: 1024,
+    };
+
+    // The puzzle's, apart from whatever `settings` give.
+    pub fn from_settings(settings: &Settings) -> Setup {
+        Setup {
+            size: settings.get("size").unwrap_or(Setup::PUZZLE.size),
+            fallen: settings.get("fallen").unwrap_or(Setup::PUZZLE.fallen),
+        }
+    }
+
+    fn space(&self, coordinates: &[(usize, usize)], fallen: usize) -> MemorySpace {
+        let mut space = MemorySpace::new(self.size, self.size);
+        space.set_coordinates(&coordinates[..fallen.min(coordinates.len())]);
+        space
+    }
+
+    // Fewest steps to the exit once `fallen` bytes are down.
+    pub fn steps(&self, coordinates: &[(usize, usize)]) -> Option<usize> {
+        self.space(coordinates, self.fallen)
+            .find_path()
+            .map(|path| path.len() - 1)
+    }
+
+    // The index of the first byte after `fallen` that cuts off the exit.
+    pub fn first_blocking(&self, coordinates: &[(usize, usize)]) -> Option<usize> {
+        (self.fallen..coordinates.len())
+            .find(|&i| self.space(coordinates, i + 1).find_path().is_none())
+    }
+}
+
+// Every byte has to land inside a `size` by `size` space.
+pub fn parse_coordinates(input: &str, size: usize) -> Result<Vec<(usize, usize)>, ParseError> {
+    parse::lines(input)
+        .map(|line| {
+            let (x, y) = line.split_once(line.text, ",")?;
+            let expected = format!("a coordinate below {}", size);
+            let coordinate = |part| match line.parse(part, "a coordinate")? {
+                value if value < size => Ok(value),
+                _ => Err(line.error(part, expected.as_str())),
+            };
+            Ok((coordinate(x)?, coordinate(y)?))
+        })
+        .collect()
+}
+
+pub struct DayEighteen;
+
+impl Solution for DayEighteen {
+    type Input = (Setup, Vec<(usize, usize)>);
+    type PartOne = Option<usize>;
+    type PartTwo = Option<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        DayEighteen::parse_with(input, &Settings::default())
+    }
+
+    fn parse_with(input: &str, settings: &Settings) -> Result<Self::Input, ParseError> {
+        let setup = Setup::from_settings(settings);
+        Ok((setup, parse_coordinates(input, setup.size)?))
+    }
+
+    fn part_one((setup, all_coordinates): &Self::Input) -> Self::PartOne {
+        setup.steps(all_coordinates)
+    }
+
+    fn part_two((setup, all_coordinates): &Self::Input) -> Self::PartTwo {
+        setup.first_blocking(all_coordinates).map(|i| {
+            let (x, y) = all_coordinates[i];
+            format!("{},{}", x, y)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maze_solution() {
+        let mut grid = MemorySpace::new(7, 7);
+        let coordinates = vec![
+            (5, 4),
+            (4, 2),
+            (4, 5),
+            (3, 0),
+            (2, 1),
+            (6, 3),
+            (2, 4),
+            (1, 5),
+            (0, 6),
+            (3, 3),
+            (2, 6),
+            (5, 1),
+        ];
+
+        grid.set_coordinates(&coordinates);
+        println!("Test maze layout:");
+
+        let path = grid.find_path().expect("Should find a path");
+        println!("Path found: {:?}", path);
+        assert_eq!(path.len() - 1, 22, "Path should take 22 steps");
+    }
+
+    #[test]
+    fn test_bytes_outside_the_space() {
+        let example = Settings::default().with("size", 7).with("fallen", 12);
+        assert!(DayEighteen::parse_with("6,6\n", &example).is_ok());
+        assert_eq!(
+            DayEighteen::parse_with("6,6\n3,7\n", &example).unwrap_err(),
+            ParseError::new(2, 3, "a coordinate below 7", "\"7\"")
+        );
+    }
+}
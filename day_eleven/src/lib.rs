@@ -0,0 +1,112 @@
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::HashMap;
+
+pub fn input_to_map(input: &str) -> Result<HashMap<i64, i64>, ParseError> {
+    let mut map = HashMap::new();
+    for line in parse::lines(input) {
+        for x in line.text.split_whitespace() {
+            let n: i64 = line.parse(x, "a stone number")?;
+            *map.entry(n).or_insert(0) += 1;
+        }
+    }
+    Ok(map)
+}
+
+pub fn process_numbers(map: HashMap<i64, i64>) -> HashMap<i64, i64> {
+    let mut new_map = HashMap::new();
+
+    for (num, count) in map {
+        if num == 0 {
+            *new_map.entry(1).or_insert(0) += count;
+        } else if count_digits(num).is_multiple_of(2) {
+            let (left, right) = split_number(num);
+            *new_map.entry(left).or_insert(0) += count;
+            *new_map.entry(right).or_insert(0) += count;
+        } else {
+            *new_map.entry(num * 2024).or_insert(0) += count;
+        }
+    }
+
+    new_map
+}
+
+pub fn count_total(map: &HashMap<i64, i64>) -> i64 {
+    map.values().sum()
+}
+
+fn count_digits(mut n: i64) -> usize {
+    if n == 0 {
+        return 1;
+    }
+    let mut count = 0;
+    n = n.abs();
+    while n > 0 {
+        n /= 10;
+        count += 1;
+    }
+    count
+}
+
+fn split_number(n: i64) -> (i64, i64) {
+    let digit_count = count_digits(n);
+    let mid = digit_count / 2;
+    let divisor = 10_i64.pow(mid as u32);
+    let right = n % divisor;
+    let left = n / divisor;
+    (left, right)
+}
+
+pub struct DayEleven;
+
+impl DayEleven {
+    fn blink(number_counts: &HashMap<i64, i64>, iterations: usize) -> i64 {
+        let mut number_counts = number_counts.clone();
+        for _ in 0..iterations {
+            number_counts = process_numbers(number_counts);
+        }
+        count_total(&number_counts)
+    }
+}
+
+impl Solution for DayEleven {
+    type Input = HashMap<i64, i64>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_to_map(input)
+    }
+
+    fn part_one(number_counts: &Self::Input) -> Self::PartOne {
+        DayEleven::blink(number_counts, 25)
+    }
+
+    fn part_two(number_counts: &Self::Input) -> Self::PartTwo {
+        DayEleven::blink(number_counts, 75)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn six_iterations() {
+        let input = "125 17";
+        let mut number_counts = input_to_map(input).unwrap();
+        for _ in 0..6 {
+            number_counts = process_numbers(number_counts);
+        }
+        assert_eq!(count_total(&number_counts), 22);
+    }
+
+    #[test]
+    fn twenty_five_iterations() {
+        let input = "125 17";
+        let mut number_counts = input_to_map(input).unwrap();
+        for _ in 0..25 {
+            number_counts = process_numbers(number_counts);
+        }
+        assert_eq!(count_total(&number_counts), 55312);
+    }
+}
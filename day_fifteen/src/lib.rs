@@ -0,0 +1,181 @@
+use aoc_common::{input, parse, InputSource, LoadError, ParseError, Solution, Unsolved};
+use colored::Colorize;
+use grid::{Direction, Grid, Point};
+
+type BoxMove = (Point, Point);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Empty,
+    Wall,
+    Box,
+}
+
+#[derive(Clone)]
+pub struct Warehouse {
+    tiles: Grid<Tile>,
+    robot_pos: Point,
+}
+
+impl Warehouse {
+    pub fn load(source: impl InputSource) -> Result<Warehouse, LoadError> {
+        input::load(source, Warehouse::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Warehouse, ParseError> {
+        let mut robot_pos = None;
+        let tiles = Grid::try_parse(input, "'#', 'O', '@' or '.'", |point, ch| match ch {
+            '#' => Some(Tile::Wall),
+            'O' => Some(Tile::Box),
+            '@' => {
+                robot_pos = Some(point);
+                Some(Tile::Empty)
+            }
+            '.' => Some(Tile::Empty),
+            _ => None,
+        })?;
+        let robot_pos = robot_pos.ok_or_else(|| ParseError::whole_input("a robot '@'", "none"))?;
+        Ok(Warehouse { tiles, robot_pos })
+    }
+
+    pub fn display(&self) {
+        let rendered = self.tiles.render(|pos, tile| {
+            if pos == self.robot_pos {
+                return "@".bright_magenta();
+            }
+            match tile {
+                Tile::Wall => "#".bright_black(),
+                Tile::Box => "0".bright_cyan(),
+                Tile::Empty => ".".white().dimmed(),
+            }
+        });
+        print!("{}", rendered);
+    }
+
+    pub fn load_moves(source: impl InputSource) -> Result<Vec<Direction>, LoadError> {
+        input::load(source, Warehouse::moves_from_str)
+    }
+
+    pub fn moves_from_str(content: &str) -> Result<Vec<Direction>, ParseError> {
+        let mut moves = Vec::new();
+        for line in parse::lines(content) {
+            for (i, c) in line.text.char_indices() {
+                if c.is_whitespace() {
+                    continue;
+                }
+                let direction = Direction::try_from(c).map_err(|_| {
+                    line.error(
+                        &line.text[i..i + c.len_utf8()],
+                        "a move ('^', 'v', '<' or '>')",
+                    )
+                })?;
+                moves.push(direction);
+            }
+        }
+        Ok(moves)
+    }
+
+    fn try_move(&self, direction: Direction) -> Result<Vec<BoxMove>, String> {
+        let new_pos = self.robot_pos + direction.delta();
+
+        match self.tiles.get(new_pos) {
+            None => Err("Position outside warehouse bounds".to_string()),
+            Some(Tile::Wall) => Err("Cannot move into wall".to_string()),
+            Some(Tile::Empty) => Ok(Vec::new()),
+            Some(Tile::Box) => {
+                let mut box_moves = Vec::new();
+                let mut current_pos = new_pos;
+
+                loop {
+                    let next_pos = current_pos + direction.delta();
+                    let next_tile = match self.tiles.get(next_pos) {
+                        None => return Err("Box position outside warehouse bounds".to_string()),
+                        Some(Tile::Wall) => return Err("Cannot push boxes into wall".to_string()),
+                        Some(tile) => *tile,
+                    };
+
+                    box_moves.push((current_pos, next_pos));
+
+                    if next_tile != Tile::Box {
+                        break;
+                    }
+
+                    current_pos = next_pos;
+                }
+
+                Ok(box_moves)
+            }
+        }
+    }
+
+    pub fn step(&mut self, direction: Direction) -> Result<(), String> {
+        let box_moves = self.try_move(direction)?;
+
+        for &(old_pos, new_pos) in box_moves.iter().rev() {
+            self.tiles[old_pos] = Tile::Empty;
+            self.tiles[new_pos] = Tile::Box;
+        }
+
+        self.robot_pos += direction.delta();
+
+        Ok(())
+    }
+
+    pub fn calculate_score(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|(_, tile)| **tile == Tile::Box)
+            .map(|(pos, _)| 100 * pos.y as usize + pos.x as usize)
+            .sum()
+    }
+}
+
+pub struct DayFifteen;
+
+impl Solution for DayFifteen {
+    type Input = (Warehouse, Vec<Direction>);
+    type PartOne = usize;
+    type PartTwo = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (map, moves, map_lines) = parse::split_sections(input).unwrap_or((input, "", 0));
+        Ok((
+            Warehouse::parse(map)?,
+            Warehouse::moves_from_str(moves).map_err(|e| e.offset(map_lines))?,
+        ))
+    }
+
+    fn part_one((warehouse, moves): &Self::Input) -> Self::PartOne {
+        let mut warehouse = warehouse.clone();
+        for movement in moves {
+            let _ = warehouse.step(*movement);
+        }
+        warehouse.calculate_score()
+    }
+
+    fn part_two(_: &Self::Input) -> Self::PartTwo {
+        Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fixture;
+
+    #[test]
+    fn test_example() {
+        let warehouse = Warehouse::load(fixture!("test_map.txt")).unwrap();
+        let moves = Warehouse::load_moves(fixture!("test_input.txt")).unwrap();
+        assert_eq!(DayFifteen::part_one(&(warehouse, moves)), 2028);
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        let input = include_str!("../example_1.txt").replace('\n', "\r\n");
+        assert_eq!(
+            DayFifteen::part_one(&DayFifteen::parse(&input).unwrap()),
+            10092
+        );
+    }
+}
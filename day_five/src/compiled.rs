@@ -0,0 +1,126 @@
+use crate::PageOrder;
+use rayon::prelude::*;
+
+// Pages above this are too many for a dense matrix, which takes a bit for
+// every pair of pages.
+pub const MAX_PAGE: u32 = 1 << 14;
+
+// The rules as a bit matrix indexed by page number, for checking many updates
+// against the same rules. Row `p` has a bit set for every page that has to
+// come after `p`.
+#[derive(Debug, Clone)]
+pub struct CompiledOrder {
+    pages: usize,
+    words: usize,
+    after: Vec<u64>,
+}
+
+impl CompiledOrder {
+    // None when a rule mentions a page above `MAX_PAGE`.
+    pub fn new(page_order: &PageOrder) -> Option<Self> {
+        let largest = page_order
+            .graph
+            .keys()
+            .chain(page_order.reverse_graph.keys())
+            .max();
+        let pages = match largest {
+            Some(&page) if page > MAX_PAGE => return None,
+            Some(&page) => page as usize + 1,
+            None => 0,
+        };
+        let words = pages.div_ceil(64);
+        let mut after = vec![0; pages * words];
+        for (&before, pages_after) in &page_order.graph {
+            let row = &mut after[before as usize * words..][..words];
+            for &page in pages_after {
+                row[page as usize / 64] |= 1 << (page % 64);
+            }
+        }
+        Some(CompiledOrder {
+            pages,
+            words,
+            after,
+        })
+    }
+
+    fn row(&self, page: u32) -> Option<&[u64]> {
+        let page = page as usize;
+        (page < self.pages).then(|| &self.after[page * self.words..][..self.words])
+    }
+
+    // One pass over the pages, keeping a set of the ones already seen: an
+    // update is out of order as soon as a page has to come before one of them.
+    pub fn is_valid(&self, pages: &[u32]) -> bool {
+        self.is_valid_with(pages, &mut vec![0; self.words])
+    }
+
+    // `seen` is scratch space of `words` zeroed words, left zeroed again.
+    fn is_valid_with(&self, pages: &[u32], seen: &mut [u64]) -> bool {
+        let mut valid = true;
+        for &page in pages {
+            let Some(row) = self.row(page) else {
+                continue;
+            };
+            if row
+                .iter()
+                .zip(seen.iter())
+                .any(|(after, seen)| after & seen != 0)
+            {
+                valid = false;
+                break;
+            }
+            seen[page as usize / 64] |= 1 << (page % 64);
+        }
+        for &page in pages {
+            if (page as usize) < self.pages {
+                seen[page as usize / 64] = 0;
+            }
+        }
+        valid
+    }
+
+    // Checks every update, spread across threads.
+    pub fn validate_all(&self, books: &[Vec<u32>]) -> Vec<bool> {
+        books
+            .par_iter()
+            .map_init(
+                || vec![0; self.words],
+                |seen, pages| self.is_valid_with(pages, seen),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::test_util::{compare, Random};
+
+    #[test]
+    fn test_matches_page_order() {
+        let mut page_order = PageOrder::new();
+        let mut random = Random::new(7);
+        for _ in 0..300 {
+            let (a, b) = (random.below(150), random.below(150));
+            if a < b {
+                page_order.add_rule(a, b);
+            }
+        }
+        let compiled = page_order.compile().unwrap();
+        let valid = compare(
+            8,
+            500,
+            |random| {
+                let pages = random.below(12);
+                (0..pages).map(|_| random.below(160)).collect::<Vec<_>>()
+            },
+            |pages| compiled.is_valid(pages),
+            |pages| page_order.is_valid(pages),
+        );
+        assert!(valid.iter().any(|&valid| valid));
+        assert!(valid.iter().any(|&valid| !valid));
+
+        page_order.add_rule(MAX_PAGE + 1, 1);
+        assert!(page_order.compile().is_none());
+    }
+}
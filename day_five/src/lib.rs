@@ -0,0 +1,503 @@
+pub mod compiled;
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{ParseError, Solution};
+use compiled::CompiledOrder;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Default)]
+pub struct PageOrder {
+    graph: HashMap<u32, HashSet<u32>>,
+    reverse_graph: HashMap<u32, HashSet<u32>>,
+}
+
+impl PageOrder {
+    pub fn new() -> Self {
+        PageOrder {
+            graph: HashMap::new(),
+            reverse_graph: HashMap::new(),
+        }
+    }
+
+    pub fn add_rule(&mut self, before: u32, after: u32) {
+        self.graph.entry(before).or_default().insert(after);
+        self.reverse_graph.entry(after).or_default().insert(before);
+    }
+
+    // Whether any rule says where `page` goes.
+    pub fn mentions(&self, page: u32) -> bool {
+        self.graph.contains_key(&page) || self.reverse_graph.contains_key(&page)
+    }
+
+    pub fn is_valid(&self, pages: &[u32]) -> bool {
+        self.violations(pages).is_empty()
+    }
+
+    pub fn compile(&self) -> Option<CompiledOrder> {
+        CompiledOrder::new(self)
+    }
+
+    // Whether each book is in order, checked in parallel, and against the
+    // compiled rules unless the page numbers are too large for them.
+    pub fn validate_all(&self, books: &[Vec<u32>]) -> Vec<bool> {
+        match self.compile() {
+            Some(compiled) => compiled.validate_all(books),
+            None => books.par_iter().map(|pages| self.is_valid(pages)).collect(),
+        }
+    }
+
+    // Every rule the pages break, ordered by where the page that should have
+    // come first is.
+    pub fn violations(&self, pages: &[u32]) -> Vec<Violation> {
+        let positions = positions(pages);
+        let mut violations = Vec::new();
+        for (before_index, &before) in pages.iter().enumerate() {
+            let Some(after) = self.graph.get(&before) else {
+                continue;
+            };
+            for &after in after {
+                for &after_index in positions.get(&after).into_iter().flatten() {
+                    if after_index < before_index {
+                        violations.push(Violation {
+                            before,
+                            after,
+                            before_index,
+                            after_index,
+                        });
+                    }
+                }
+            }
+        }
+        violations.sort_by_key(|v| (v.before_index, v.after_index));
+        violations
+    }
+
+    // The pages in an order that follows every rule between them, found by a
+    // topological sort of the rules restricted to these pages. Pages the rules
+    // don't order keep their relative order.
+    pub fn fix_order(&self, pages: &[u32]) -> Result<Vec<u32>, CycleError> {
+        Ok(self.sort(pages)?.into_iter().map(|i| pages[i]).collect())
+    }
+
+    // `fix_order` as indices into `pages`.
+    fn sort(&self, pages: &[u32]) -> Result<Vec<usize>, CycleError> {
+        let positions = positions(pages);
+        // How many pages in the book have to come before each one.
+        let mut waiting_on = vec![0; pages.len()];
+        for (i, &page) in pages.iter().enumerate() {
+            if let Some(before) = self.reverse_graph.get(&page) {
+                waiting_on[i] = before
+                    .iter()
+                    .filter_map(|before| positions.get(before))
+                    .map(Vec::len)
+                    .sum();
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..pages.len())
+            .filter(|&i| waiting_on[i] == 0)
+            .map(Reverse)
+            .collect();
+        let mut fixed = Vec::with_capacity(pages.len());
+        while let Some(Reverse(i)) = ready.pop() {
+            fixed.push(i);
+            let Some(after) = self.graph.get(&pages[i]) else {
+                continue;
+            };
+            for j in after
+                .iter()
+                .filter_map(|after| positions.get(after))
+                .flatten()
+            {
+                waiting_on[*j] -= 1;
+                if waiting_on[*j] == 0 {
+                    ready.push(Reverse(*j));
+                }
+            }
+        }
+        if fixed.len() < pages.len() {
+            return Err(self.find_cycle(pages, &waiting_on));
+        }
+        Ok(fixed)
+    }
+
+    // Every page the sort couldn't place is still waiting on another such
+    // page, so following those back from any of them must come round in a
+    // loop.
+    fn find_cycle(&self, pages: &[u32], waiting_on: &[usize]) -> CycleError {
+        let stuck: HashSet<u32> = (0..pages.len())
+            .filter(|&i| waiting_on[i] > 0)
+            .map(|i| pages[i])
+            .collect();
+        let mut path = vec![pages[waiting_on.iter().position(|&w| w > 0).unwrap()]];
+        loop {
+            let page = *path.last().unwrap();
+            let before = self.reverse_graph[&page]
+                .iter()
+                .copied()
+                .filter(|before| stuck.contains(before))
+                .min()
+                .unwrap();
+            if let Some(start) = path.iter().position(|&p| p == before) {
+                let mut pages = path.split_off(start);
+                pages.reverse();
+                return CycleError { pages };
+            }
+            path.push(before);
+        }
+    }
+
+    // The fewest moves that put the pages in the order `fix_order` gives,
+    // applied one after the other. The pages that stay put are the longest
+    // run, not necessarily contiguous, already in that order; that's as few as
+    // possible whenever the rules order every pair of pages in the book, as
+    // the puzzle's do.
+    pub fn moves(&self, pages: &[u32]) -> Result<Vec<Move>, CycleError> {
+        let order = self.sort(pages)?;
+        let mut rank = vec![0; pages.len()];
+        for (r, &i) in order.iter().enumerate() {
+            rank[i] = r;
+        }
+        let mut placed = vec![false; pages.len()];
+        for i in longest_increasing(&rank) {
+            placed[i] = true;
+        }
+
+        let mut current: Vec<usize> = (0..pages.len()).collect();
+        let mut moves = Vec::new();
+        for &i in &order {
+            if placed[i] {
+                continue;
+            }
+            let from = current.iter().position(|&j| j == i).unwrap();
+            current.remove(from);
+            let to = current
+                .iter()
+                .rposition(|&j| placed[j] && rank[j] < rank[i])
+                .map_or(0, |k| k + 1);
+            current.insert(to, i);
+            placed[i] = true;
+            moves.push(Move {
+                page: pages[i],
+                from,
+                to,
+            });
+        }
+        Ok(moves)
+    }
+}
+
+fn positions(pages: &[u32]) -> HashMap<u32, Vec<usize>> {
+    let mut positions: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, &page) in pages.iter().enumerate() {
+        positions.entry(page).or_default().push(i);
+    }
+    positions
+}
+
+// Indices of a longest strictly increasing subsequence.
+fn longest_increasing(values: &[usize]) -> Vec<usize> {
+    // The index ending the best subsequence of each length so far, and the
+    // index before each one in its subsequence.
+    let mut ends: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+    for i in 0..values.len() {
+        let length = ends.partition_point(|&j| values[j] < values[i]);
+        if length > 0 {
+            previous[i] = Some(ends[length - 1]);
+        }
+        if length == ends.len() {
+            ends.push(i);
+        } else {
+            ends[length] = i;
+        }
+    }
+    let mut indices = Vec::new();
+    let mut next = ends.last().copied();
+    while let Some(i) = next {
+        indices.push(i);
+        next = previous[i];
+    }
+    indices.reverse();
+    indices
+}
+
+// A broken rule: `before` is at `before_index`, after `after` at
+// `after_index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub before: u32,
+    pub after: u32,
+    pub before_index: usize,
+    pub after_index: usize,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{} is broken by {} at {} coming after {} at {}",
+            self.before, self.after, self.before, self.before_index, self.after, self.after_index
+        )
+    }
+}
+
+// Take `page` out from index `from` and put it back in at index `to`,
+// counted once it's been taken out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub page: u32,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Move {
+    pub fn apply(&self, pages: &mut Vec<u32>) {
+        let page = pages.remove(self.from);
+        pages.insert(self.to, page);
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.page, self.from, self.to)
+    }
+}
+
+// Rules that contradict each other: each page has to come before the next,
+// and the last before the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    pub pages: Vec<u32>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the rules")?;
+        for (i, page) in self.pages.iter().enumerate() {
+            let next = self.pages[(i + 1) % self.pages.len()];
+            write!(f, " {}|{}", page, next)?;
+        }
+        write!(f, " can't all be followed")
+    }
+}
+
+impl Error for CycleError {}
+
+pub fn parse_rules(input: &str) -> Result<PageOrder, ParseError> {
+    parse_rules_with(input, &mut Vec::new())
+}
+
+// Repeated rules are harmless, so they only get a warning.
+fn parse_rules_with(input: &str, warnings: &mut Vec<ParseError>) -> Result<PageOrder, ParseError> {
+    let mut page_order = PageOrder::new();
+    let mut seen = HashSet::new();
+    for line in parse::lines(input) {
+        if !line.text.contains('|') && line.text.contains(',') {
+            return Err(line.error(line.text, "a blank line between the rules and the updates"));
+        }
+        let (before, after) = line.split_once(line.text, "|")?;
+        let before = line.parse(before, "a page number")?;
+        let after = line.parse(after, "a page number")?;
+        if before == after {
+            return Err(line.error(line.text, "a rule between two different pages"));
+        }
+        if !seen.insert((before, after)) {
+            warnings.push(line.error(line.text, "each rule once"));
+        }
+        page_order.add_rule(before, after);
+    }
+    Ok(page_order)
+}
+
+// The updates, one to a line, skipping blank lines.
+fn book_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    parse::lines(input).filter(|line| !line.text.trim().is_empty())
+}
+
+pub fn parse_books(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    book_lines(input)
+        .map(|line| {
+            line.text
+                .split(',')
+                .map(|s| line.parse(s, "a page number"))
+                .collect()
+        })
+        .collect()
+}
+
+// The puzzle input: the rules, a blank line, then the updates. Either half can
+// come from its own file, as the runner joins them the same way.
+pub struct Manual {
+    pub page_order: PageOrder,
+    pub books: Vec<Vec<u32>>,
+    // Things that look like mistakes but don't stop the puzzle being solved:
+    // repeated rules, and pages in the updates that no rule mentions.
+    pub warnings: Vec<ParseError>,
+}
+
+pub fn parse_manual(input: &str) -> Result<Manual, ParseError> {
+    let (rules, books, offset) = parse::split_sections(input).unwrap_or((input, "", 0));
+    let mut warnings = Vec::new();
+    let page_order = parse_rules_with(rules, &mut warnings)?;
+    let parsed = parse_books(books).map_err(|e| e.offset(offset))?;
+
+    let mut reported = HashSet::new();
+    for (line, pages) in book_lines(books).zip(&parsed) {
+        for (part, &page) in line.text.split(',').zip(pages) {
+            if !page_order.mentions(page) && reported.insert(page) {
+                warnings.push(line.error(part, "a page the rules mention").offset(offset));
+            }
+        }
+    }
+    Ok(Manual {
+        page_order,
+        books: parsed,
+        warnings,
+    })
+}
+
+pub struct DayFive;
+
+impl Solution for DayFive {
+    type Input = (PageOrder, Vec<Vec<u32>>);
+    type PartOne = u64;
+    // None when a book's pages can't be put in order.
+    type PartTwo = Option<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let manual = parse_manual(input)?;
+        Ok((manual.page_order, manual.books))
+    }
+
+    fn part_one((page_order, books): &Self::Input) -> Self::PartOne {
+        books
+            .iter()
+            .zip(page_order.validate_all(books))
+            .filter(|(_, valid)| *valid)
+            .map(|(pages, _)| pages[pages.len() / 2] as u64)
+            .sum()
+    }
+
+    fn part_two((page_order, books): &Self::Input) -> Self::PartTwo {
+        books
+            .iter()
+            .zip(page_order.validate_all(books))
+            .filter(|(_, valid)| !valid)
+            .map(|(pages, _)| {
+                let fixed = page_order.fix_order(pages).ok()?;
+                Some(fixed[fixed.len() / 2] as u64)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(rules: &[(u32, u32)]) -> PageOrder {
+        let mut page_order = PageOrder::new();
+        for &(before, after) in rules {
+            page_order.add_rule(before, after);
+        }
+        page_order
+    }
+
+    #[test]
+    fn test_fix_order() {
+        let page_order = rules(&[(47, 53), (97, 13), (97, 61), (97, 47), (75, 29), (61, 13)]);
+        assert_eq!(
+            page_order.fix_order(&[61, 13, 47, 97, 53]),
+            Ok(vec![97, 61, 13, 47, 53])
+        );
+        assert_eq!(page_order.fix_order(&[29, 75, 5]), Ok(vec![75, 29, 5]));
+        assert_eq!(page_order.fix_order(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_violations() {
+        let page_order = rules(&[(47, 53), (97, 13), (97, 61), (97, 47), (75, 29), (61, 13)]);
+        let broken: Vec<(u32, u32, usize, usize)> = page_order
+            .violations(&[61, 13, 47, 97, 53])
+            .iter()
+            .map(|v| (v.before, v.after, v.before_index, v.after_index))
+            .collect();
+        assert_eq!(broken, [(97, 61, 3, 0), (97, 13, 3, 1), (97, 47, 3, 2)]);
+        assert!(page_order.is_valid(&[97, 61, 13, 47, 53]));
+    }
+
+    #[test]
+    fn test_moves() {
+        let page_order = rules(&[(1, 2), (2, 3), (3, 4), (1, 3), (1, 4), (2, 4)]);
+        for pages in [[4, 1, 2, 3], [2, 3, 4, 1], [4, 3, 2, 1], [1, 2, 3, 4]] {
+            let moves = page_order.moves(&pages).unwrap();
+            let mut fixed = pages.to_vec();
+            for m in &moves {
+                m.apply(&mut fixed);
+            }
+            assert_eq!(fixed, [1, 2, 3, 4]);
+            assert!(page_order.is_valid(&fixed));
+            let expected = match pages {
+                [4, 3, 2, 1] => 3,
+                [1, 2, 3, 4] => 0,
+                _ => 1,
+            };
+            assert_eq!(moves.len(), expected, "{:?}", pages);
+        }
+    }
+
+    #[test]
+    fn test_parse_manual() {
+        let manual = parse_manual("1|2\n2|3\n1|2\n\n1,2,3\n3,4,2\n4,1\n").unwrap();
+        assert_eq!(manual.books.len(), 3);
+        let warnings: Vec<String> = manual.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            warnings,
+            [
+                "input:3:1: expected each rule once, found \"1|2\"",
+                "input:6:3: expected a page the rules mention, found \"4\""
+            ]
+        );
+
+        let crlf = parse_manual("1|2\r\n2|3\r\n\r\n1,2,3\r\n3,2\r\n").unwrap();
+        assert_eq!(crlf.books, [vec![1, 2, 3], vec![3, 2]]);
+        assert!(crlf.warnings.is_empty());
+        let blank_lines = parse_manual("1|2\n\n1,2\n\n2,1\n\n").unwrap();
+        assert_eq!(blank_lines.books, [vec![1, 2], vec![2, 1]]);
+
+        let error = |input| parse_manual(input).err().map(|e| e.to_string());
+        assert_eq!(
+            error("1|2\n3|3\n"),
+            Some("input:2:1: expected a rule between two different pages, found \"3|3\"".into())
+        );
+        assert_eq!(
+            error("1|2\n1,2\n"),
+            Some(
+                "input:2:1: expected a blank line between the rules and the updates, found \"1,2\""
+                    .into()
+            )
+        );
+        assert_eq!(
+            error("1|2\n1-2\n"),
+            Some("input:2:1: expected \"|\", found \"1-2\"".into())
+        );
+    }
+
+    #[test]
+    fn test_cycle_error() {
+        let page_order = rules(&[(1, 2), (2, 3), (3, 1), (3, 4), (5, 1)]);
+        let error = page_order.fix_order(&[4, 3, 2, 1, 5]).unwrap_err();
+        assert_eq!(error.pages, [1, 2, 3]);
+        assert_eq!(
+            error.to_string(),
+            "the rules 1|2 2|3 3|1 can't all be followed"
+        );
+        // Pages outside the book don't count.
+        assert!(page_order.fix_order(&[3, 2, 4]).is_ok());
+    }
+}
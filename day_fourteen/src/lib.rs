@@ -0,0 +1,145 @@
+use aoc_common::parse::{self, Line};
+use aoc_common::{ParseError, Settings, Solution, Unsolved};
+
+#[cfg(feature = "visualize")]
+mod visualize;
+
+#[derive(Debug, Clone)]
+pub struct AreaMap {
+    height: usize,
+    width: usize,
+    state: Vec<Actor>,
+}
+
+#[derive(Debug, Clone)]
+struct Actor {
+    position: (usize, usize),
+    velocity: (i32, i32),
+}
+
+impl AreaMap {
+    pub fn new(height: usize, width: usize) -> Self {
+        AreaMap {
+            height,
+            width,
+            state: Vec::new(),
+        }
+    }
+
+    pub fn parse(input: &str, width: usize, height: usize) -> Result<Self, ParseError> {
+        let mut map = AreaMap::new(height, width);
+        map.load_actors(input)?;
+        Ok(map)
+    }
+
+    // Every robot has to start inside the area.
+    pub fn load_actors(&mut self, input: &str) -> Result<(), ParseError> {
+        for line in parse::lines(input) {
+            let actor = Actor::parse(&line)?;
+            if actor.position.0 >= self.width || actor.position.1 >= self.height {
+                return Err(line.error(
+                    line.text,
+                    format!("a robot inside the {}x{} area", self.width, self.height),
+                ));
+            }
+            self.state.push(actor);
+        }
+        Ok(())
+    }
+
+    fn update(&mut self) {
+        for actor in &mut self.state {
+            actor.update(self.width, self.height);
+        }
+    }
+
+    pub fn find_pattern(&mut self) -> usize {
+        let mut ticks = 0;
+        let total_actors = self.state.len();
+        let threshold = (total_actors * 9) / 10;
+
+        loop {
+            let mut adjacent_count = 0;
+
+            for i in 0..self.state.len() {
+                for j in (i + 1)..self.state.len() {
+                    let (x1, y1) = self.state[i].position;
+                    let (x2, y2) = self.state[j].position;
+
+                    if (x1.abs_diff(x2) <= 1) && (y1.abs_diff(y2) <= 1) {
+                        adjacent_count += 1;
+                    }
+                }
+            }
+
+            if adjacent_count > threshold {
+                return ticks;
+            }
+
+            self.update();
+            ticks += 1;
+
+            if ticks > 1_000_000 {
+                println!("Hit safety limit without finding pattern");
+                return ticks;
+            }
+        }
+    }
+}
+
+impl Actor {
+    fn new(position: (usize, usize), velocity: (i32, i32)) -> Self {
+        Actor { position, velocity }
+    }
+
+    fn parse(line: &Line) -> Result<Self, ParseError> {
+        let (position_string, velocity_string) = line.split_once(line.text.trim(), " ")?;
+        let position_string = line.strip_prefix(position_string, "p=")?;
+        let (x, y) = line.split_once(position_string, ",")?;
+        let pos = (line.parse(x, "a position")?, line.parse(y, "a position")?);
+        let velocity_string = line.strip_prefix(velocity_string.trim(), "v=")?;
+        let (dx, dy) = line.split_once(velocity_string, ",")?;
+        let vel = (line.parse(dx, "a velocity")?, line.parse(dy, "a velocity")?);
+        Ok(Actor::new(pos, vel))
+    }
+
+    fn update(&mut self, width: usize, height: usize) {
+        let x = self.position.0 as i32;
+        let y = self.position.1 as i32;
+        let new_x = (x + self.velocity.0).rem_euclid(width as i32) as usize;
+        let new_y = (y + self.velocity.1).rem_euclid(height as i32) as usize;
+        self.position = (new_x, new_y);
+    }
+}
+
+// The puzzle's area; the example's is 11x7.
+pub const WIDTH: usize = 101;
+pub const HEIGHT: usize = 103;
+
+pub struct DayFourteen;
+
+impl Solution for DayFourteen {
+    type Input = AreaMap;
+    type PartOne = Unsolved;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        AreaMap::parse(input, WIDTH, HEIGHT)
+    }
+
+    fn parse_with(input: &str, settings: &Settings) -> Result<Self::Input, ParseError> {
+        AreaMap::parse(
+            input,
+            settings.get("width").unwrap_or(WIDTH),
+            settings.get("height").unwrap_or(HEIGHT),
+        )
+    }
+
+    fn part_one(_: &Self::Input) -> Self::PartOne {
+        Unsolved
+    }
+
+    fn part_two(map: &Self::Input) -> Self::PartTwo {
+        map.clone().find_pattern()
+    }
+}
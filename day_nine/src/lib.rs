@@ -0,0 +1,269 @@
+use aoc_common::parse::Line;
+use aoc_common::{ParseError, Solution};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct FileNode {
+    pub id: usize,
+    pub size: i32,
+    pub free_size: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockPosition {
+    id: usize,
+    start: usize,
+    length: usize,
+}
+
+impl fmt::Display for FileNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bars = "|".repeat(self.size as usize);
+        let dots = ".".repeat(self.free_size as usize);
+        write!(f, "{}{}", bars, dots)
+    }
+}
+
+pub fn compact_blocks(original_nodes: HashMap<usize, FileNode>) -> Vec<BlockPosition> {
+    let mut layout = Vec::new();
+    let mut positions = Vec::new();
+
+    let max_id = *original_nodes.keys().max().unwrap_or(&0);
+
+    for id in 0..=max_id {
+        if let Some(node) = original_nodes.get(&id) {
+            let start = layout.len();
+            for _ in 0..node.size {
+                layout.push(Some(id));
+            }
+            positions.push(BlockPosition {
+                id,
+                start,
+                length: node.size as usize,
+            });
+            for _ in 0..node.free_size {
+                layout.push(None);
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+
+    for id in (0..=max_id).rev() {
+        if let Some(node) = original_nodes.get(&id) {
+            let mut orig_start = None;
+            for (i, slot) in layout.iter_mut().enumerate() {
+                if *slot == Some(id) {
+                    if orig_start.is_none() {
+                        orig_start = Some(i);
+                    }
+                    *slot = None;
+                }
+            }
+
+            let mut blocks_to_place = node.size as usize;
+
+            let mut pos = 0;
+            while blocks_to_place > 0 {
+                while pos < layout.len() && layout[pos].is_some() {
+                    pos += 1;
+                }
+
+                if pos >= layout.len() {
+                    break;
+                }
+                let mut free_count = 0;
+                let start_pos = pos;
+                while pos < layout.len() && layout[pos].is_none() {
+                    free_count += 1;
+                    pos += 1;
+                }
+                let blocks_to_place_here = blocks_to_place.min(free_count);
+                if blocks_to_place_here > 0 {
+                    for slot in &mut layout[start_pos..start_pos + blocks_to_place_here] {
+                        *slot = Some(id);
+                    }
+                    result.push(BlockPosition {
+                        id,
+                        start: start_pos,
+                        length: blocks_to_place_here,
+                    });
+
+                    blocks_to_place -= blocks_to_place_here;
+                }
+            }
+        }
+    }
+
+    result.sort_by_key(|b| b.start);
+    result
+}
+
+pub fn compact_blocks_no_split(original_nodes: HashMap<usize, FileNode>) -> Vec<BlockPosition> {
+    let mut layout = Vec::new();
+    let mut positions = Vec::new();
+    let max_id = *original_nodes.keys().max().unwrap_or(&0);
+
+    for id in 0..=max_id {
+        if let Some(node) = original_nodes.get(&id) {
+            let start = layout.len();
+
+            for _ in 0..node.size {
+                layout.push(Some(id));
+            }
+
+            positions.push(BlockPosition {
+                id,
+                start,
+                length: node.size as usize,
+            });
+
+            for _ in 0..node.free_size {
+                layout.push(None);
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+
+    for id in (0..=max_id).rev() {
+        if let Some(node) = original_nodes.get(&id) {
+            let mut current_pos = 0;
+            while current_pos < layout.len() && layout[current_pos] != Some(id) {
+                current_pos += 1;
+            }
+
+            let mut best_pos = current_pos;
+
+            'outer: for try_pos in 0..current_pos {
+                if try_pos + node.size as usize > current_pos {
+                    break;
+                }
+
+                for i in 0..node.size as usize {
+                    if layout[try_pos + i].is_some() {
+                        continue 'outer;
+                    }
+                }
+
+                best_pos = try_pos;
+                break;
+            }
+            if best_pos != current_pos {
+                for i in 0..node.size as usize {
+                    layout[current_pos + i] = None;
+                }
+
+                for i in 0..node.size as usize {
+                    layout[best_pos + i] = Some(id);
+                }
+            }
+
+            result.push(BlockPosition {
+                id,
+                start: best_pos,
+                length: node.size as usize,
+            });
+        }
+    }
+
+    result.sort_by_key(|b| b.start);
+    result
+}
+
+pub fn checksum(compacted: &[BlockPosition]) -> i64 {
+    let mut sorted = compacted.to_vec();
+    sorted.sort_by_key(|block| block.start);
+
+    sorted
+        .iter()
+        .flat_map(|block| (block.start..block.start + block.length).map(move |pos| pos * block.id))
+        .map(|x| x as i64)
+        .sum()
+}
+
+pub fn line_to_pairs(line: String) -> Result<Vec<FileNode>, ParseError> {
+    let line = Line {
+        number: 1,
+        text: &line,
+    };
+    let digits = line
+        .text
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|d| d as i32)
+                .ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], "a digit"))
+        })
+        .collect::<Result<Vec<i32>, _>>()?;
+    Ok(digits
+        .chunks(2)
+        .enumerate()
+        .map(|(id, chunk)| FileNode {
+            id,
+            size: chunk[0],
+            free_size: chunk.get(1).copied().unwrap_or(0),
+        })
+        .collect())
+}
+
+pub struct DayNine;
+
+impl Solution for DayNine {
+    type Input = HashMap<usize, FileNode>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let line = input.lines().next().unwrap_or_default().to_string();
+        Ok(line_to_pairs(line)?
+            .into_iter()
+            .map(|node| (node.id, node))
+            .collect())
+    }
+
+    fn part_one(fs: &Self::Input) -> Self::PartOne {
+        checksum(&compact_blocks(fs.clone()))
+    }
+
+    fn part_two(fs: &Self::Input) -> Self::PartTwo {
+        checksum(&compact_blocks_no_split(fs.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_compaction_and_checksum() {
+        let input = String::from("2333133121414131402");
+
+        let pairs = line_to_pairs(input).unwrap();
+
+        let original_nodes: HashMap<usize, FileNode> =
+            pairs.into_iter().map(|node| (node.id, node)).collect();
+
+        let compacted = compact_blocks(original_nodes);
+
+        let checksum = checksum(&compacted);
+
+        assert_eq!(checksum, 1928);
+    }
+
+    #[test]
+    fn test_full_compaction_no_split_and_checksum() {
+        let input = String::from("2333133121414131402");
+
+        let pairs = line_to_pairs(input).unwrap();
+
+        let original_nodes: HashMap<usize, FileNode> =
+            pairs.into_iter().map(|node| (node.id, node)).collect();
+
+        let compacted = compact_blocks_no_split(original_nodes);
+
+        let checksum = checksum(&compacted);
+        assert_eq!(checksum, 2858);
+    }
+}
@@ -0,0 +1,156 @@
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct TrieNode {
+    is_end: bool,
+    children: HashMap<char, TrieNode>,
+}
+
+impl TrieNode {
+    pub fn new() -> Self {
+        TrieNode {
+            is_end: false,
+            children: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, word: &str) {
+        let mut current = self;
+        for c in word.chars() {
+            current = current.children.entry(c).or_default()
+        }
+        current.is_end = true;
+    }
+}
+
+const STRIPES: &str = "a stripe colour (w, u, b, r or g)";
+
+fn is_stripe(c: char) -> bool {
+    matches!(c, 'w' | 'u' | 'b' | 'r' | 'g')
+}
+
+pub fn build_trie(towels: &str) -> Result<TrieNode, ParseError> {
+    let mut trie = TrieNode::new();
+    for line in parse::lines(towels) {
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        for towel in line.text.split(',') {
+            let towel = towel.trim();
+            if towel.is_empty() || !towel.chars().all(is_stripe) {
+                return Err(line.error(towel, format!("a towel made of {}", STRIPES)));
+            }
+            trie.insert(towel);
+        }
+    }
+    Ok(trie)
+}
+
+pub fn parse_designs(input: &str) -> Result<Vec<String>, ParseError> {
+    parse::lines(input)
+        .map(
+            |line| match line.text.char_indices().find(|&(_, c)| !is_stripe(c)) {
+                Some((i, c)) => Err(line.error(&line.text[i..i + c.len_utf8()], STRIPES)),
+                None => Ok(line.text.to_string()),
+            },
+        )
+        .collect()
+}
+
+pub fn find_valid_patterns(input: &str, root: &TrieNode) -> bool {
+    let n = input.len();
+    let mut dp = vec![false; n + 1];
+    dp[0] = true;
+
+    for i in 0..n {
+        if !dp[i] {
+            continue;
+        }
+
+        let mut current = root;
+        let mut j = i;
+        while j < n {
+            if let Some(next) = current.children.get(&input[j..=j].chars().next().unwrap()) {
+                if next.is_end {
+                    dp[j + 1] = true;
+                }
+                current = next;
+                j += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    dp[n]
+}
+
+pub fn count_valid_designs(input: &str, root: &TrieNode) -> u64 {
+    let n = input.len();
+    let mut dp = vec![0u64; n + 1];
+    dp[0] = 1;
+
+    for i in 0..n {
+        if dp[i] == 0 {
+            continue;
+        }
+        let mut current = root;
+        let mut j = i;
+        while j < n {
+            if let Some(next) = current.children.get(&input[j..=j].chars().next().unwrap()) {
+                if next.is_end {
+                    dp[j + 1] += dp[i];
+                }
+                current = next;
+                j += 1;
+            } else {
+                break;
+            }
+        }
+    }
+    dp[n]
+}
+
+pub struct DayNineteen;
+
+impl Solution for DayNineteen {
+    type Input = (TrieNode, Vec<String>);
+    type PartOne = usize;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (towels, designs, towel_lines) = parse::split_sections(input).unwrap_or((input, "", 0));
+        Ok((
+            build_trie(towels)?,
+            parse_designs(designs).map_err(|e| e.offset(towel_lines))?,
+        ))
+    }
+
+    fn part_one((trie, designs): &Self::Input) -> Self::PartOne {
+        designs
+            .iter()
+            .filter(|line| find_valid_patterns(line, trie))
+            .count()
+    }
+
+    fn part_two((trie, designs): &Self::Input) -> Self::PartTwo {
+        designs
+            .iter()
+            .map(|line| count_valid_designs(line, trie))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crlf_input() {
+        let input = include_str!("../example_1.txt").replace('\n', "\r\n");
+        let parsed = DayNineteen::parse(&input).unwrap();
+        assert_eq!(DayNineteen::part_one(&parsed), 6);
+        assert_eq!(DayNineteen::part_two(&parsed), 16);
+    }
+}
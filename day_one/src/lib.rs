@@ -0,0 +1,155 @@
+pub mod metrics;
+pub mod stream;
+
+use aoc_common::parse::Line;
+use aoc_common::{parse, ParseError, Solution};
+use std::collections::HashMap;
+
+// The pair of location IDs on a line, or None for a blank line.
+pub fn parse_pair(line: &Line) -> Result<Option<(i32, i32)>, ParseError> {
+    let numbers: Vec<&str> = line.text.split_whitespace().collect();
+    match numbers[..] {
+        [] => Ok(None),
+        [first, second] => Ok(Some((
+            line.parse(first, "a location ID")?,
+            line.parse(second, "a location ID")?,
+        ))),
+        [_] => Err(line.error(&line.text[line.text.len()..], "two location IDs")),
+        [_, _, extra, ..] => Err(line.error(extra, "end of line")),
+    }
+}
+
+// What to do with a line that isn't a pair of IDs. Strict parsing stops at the
+// first one, lenient parsing leaves it out of both columns and reports it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+impl Mode {
+    // Returns the error back in strict mode, otherwise adds it to `skipped`.
+    pub fn handle(
+        self,
+        error: ParseError,
+        skipped: &mut Vec<ParseError>,
+    ) -> Result<(), ParseError> {
+        match self {
+            Mode::Strict => Err(error),
+            Mode::Lenient => {
+                skipped.push(error);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Columns {
+    pub left: Vec<i32>,
+    pub right: Vec<i32>,
+    // Lines left out in lenient mode, and why.
+    pub skipped: Vec<ParseError>,
+}
+
+pub fn parse_columns(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
+    let columns = parse_columns_with(input, Mode::Strict)?;
+    Ok((columns.left, columns.right))
+}
+
+// Both columns sorted, as the puzzle compares them.
+pub fn parse_columns_with(input: &str, mode: Mode) -> Result<Columns, ParseError> {
+    let mut columns = parse_rows(input, mode)?;
+    columns.left.sort();
+    columns.right.sort();
+    Ok(columns)
+}
+
+// Both columns in the order the rows were written, which keeps each pair
+// together for the rank correlations in `metrics`.
+pub fn parse_rows(input: &str, mode: Mode) -> Result<Columns, ParseError> {
+    let mut columns = Columns::default();
+
+    for line in parse::lines(input) {
+        match parse_pair(&line) {
+            Ok(Some((first, second))) => {
+                columns.left.push(first);
+                columns.right.push(second);
+            }
+            Ok(None) => {}
+            Err(e) => mode.handle(e, &mut columns.skipped)?,
+        }
+    }
+    Ok(columns)
+}
+
+pub fn distance(left: i32, right: i32) -> u64 {
+    (left as i64 - right as i64).unsigned_abs()
+}
+
+pub fn calculate_total_distance(left: &[i32], right: &[i32]) -> u64 {
+    left.iter().zip(right).map(|(&i, &j)| distance(i, j)).sum()
+}
+
+pub fn calculate_similarity_score(left: &[i32], right: &[i32]) -> i64 {
+    let mut frequencies: HashMap<i32, i64> = HashMap::new();
+    for &value in right {
+        *frequencies.entry(value).or_insert(0) += 1;
+    }
+
+    left.iter()
+        .filter_map(|&value| frequencies.get(&value).map(|&count| count * value as i64))
+        .sum()
+}
+
+pub struct DayOne;
+
+impl Solution for DayOne {
+    type Input = (Vec<i32>, Vec<i32>);
+    type PartOne = u64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_columns(input)
+    }
+
+    fn part_one((column1, column2): &Self::Input) -> Self::PartOne {
+        calculate_total_distance(column1, column2)
+    }
+
+    fn part_two((column1, column2): &Self::Input) -> Self::PartTwo {
+        calculate_similarity_score(column1, column2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAGGED: &str = "3   4\n4   x\n2\n\n1   3 9\n3   3";
+
+    #[test]
+    fn test_strict_mode_rejects_bad_rows() {
+        let error = parse_columns_with(RAGGED, Mode::Strict).unwrap_err();
+        assert_eq!(error, ParseError::new(2, 5, "a location ID", "\"x\""));
+    }
+
+    #[test]
+    fn test_lenient_mode_reports_bad_rows() {
+        let columns = parse_columns_with(RAGGED, Mode::Lenient).unwrap();
+        assert_eq!(columns.left, [3, 3]);
+        assert_eq!(columns.right, [3, 4]);
+        let lines: Vec<usize> = columns.skipped.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [2, 3, 5]);
+    }
+
+    #[test]
+    fn test_distances_do_not_overflow() {
+        let left = [i32::MIN, i32::MAX];
+        let right = [i32::MAX, i32::MAX];
+        assert_eq!(calculate_total_distance(&left, &right), u32::MAX as u64);
+        let ids = [i32::MAX; 3];
+        assert_eq!(calculate_similarity_score(&ids, &ids), 9 * i32::MAX as i64);
+    }
+}
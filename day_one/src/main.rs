@@ -1,63 +1,168 @@
-use std::collections::HashMap;
+use aoc_common::cli::Args;
+use aoc_common::{unwrap_or_exit, LoadError, ParseError, Part};
+use clap::Parser;
+use day_one::metrics::{self, SharedId};
+use day_one::stream::{summarize, StreamOptions, Totals};
+use day_one::{
+    calculate_similarity_score, calculate_total_distance, parse_columns_with, parse_rows, Mode,
+};
 use std::fs::File;
-use std::io::{self, BufRead};
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+    /// Sort the lists on disk instead of in memory, for very large inputs
+    #[arg(long)]
+    stream: bool,
+    /// IDs per column to sort in memory at a time when streaming
+    #[arg(long, default_value_t = StreamOptions::default().chunk_size)]
+    chunk_size: usize,
+    /// Skip lines that aren't a pair of IDs and list them, instead of stopping
+    #[arg(long)]
+    lenient: bool,
+    /// Also compare the lists by overlap, rank correlation and shared IDs
+    #[arg(long)]
+    metrics: bool,
+    /// How many shared IDs to list with --metrics
+    #[arg(long, default_value_t = 5)]
+    top: usize,
+}
 
 fn main() {
-    let mut column1: Vec<i32> = Vec::new();
-    let mut column2: Vec<i32> = Vec::new();
-
-    if let Ok(file) = File::open("input.txt") {
-        let reader = io::BufReader::new(file);
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                let numbers: Vec<&str> = line.split_whitespace().collect();
-                if numbers.len() >= 2 {
-                    if let Ok(num1) = numbers[0].parse::<i32>() {
-                        column1.push(num1);
-                    }
-                    if let Ok(num2) = numbers[1].parse::<i32>() {
-                        column2.push(num2);
-                    }
-                }
-            }
-        }
+    let cli = Cli::parse();
+    let args = &cli.args;
+    let mode = if cli.lenient {
+        Mode::Lenient
     } else {
-        println!("Error: Could not open input file");
+        Mode::Strict
+    };
+    if cli.stream && cli.metrics {
+        eprintln!("--metrics needs the lists in memory, so it can't be used with --stream");
+        std::process::exit(2);
+    }
+    if cli.stream {
+        let options = StreamOptions {
+            chunk_size: cli.chunk_size,
+            mode,
+            ..StreamOptions::default()
+        };
+        let totals = unwrap_or_exit(stream(args, &options));
+        report(&totals.skipped);
+        if args.verbose > 0 {
+            println!("Read {} pairs.", totals.pairs);
+        }
+        if args.wants(Part::One) {
+            println!("Distance: {}", totals.distance);
+        }
+        if args.wants(Part::Two) {
+            println!("Similarity: {}", totals.similarity);
+        }
         return;
     }
 
-    column1.sort();
-    column2.sort();
+    let input = unwrap_or_exit(args.read(&["input.txt"]));
+    let parse = if cli.metrics {
+        parse_rows
+    } else {
+        parse_columns_with
+    };
+    let mut columns = unwrap_or_exit(input.parse(|text| parse(text, mode)));
+    if cli.metrics {
+        print_metrics(&columns.left, &columns.right, cli.top);
+        columns.left.sort();
+        columns.right.sort();
+    }
+    let skipped: Vec<ParseError> = columns
+        .skipped
+        .into_iter()
+        .map(|e| input.locate(e))
+        .collect();
+    report(&skipped);
+    let (column1, column2) = (columns.left, columns.right);
+    if args.verbose > 0 {
+        println!("Loaded {} pairs.", column1.len());
+    }
+
+    if args.wants(Part::One) {
+        let total_distance = calculate_total_distance(&column1, &column2);
+        println!("Distance: {}", total_distance);
+    }
 
-    let total_distance = calculate_total_distance(&column1, &column2);
-    let total_distance_sum = total_distance.iter().sum::<i32>();
-    println!("Distance: {}", total_distance_sum);
+    if args.wants(Part::Two) {
+        let similarity_score = calculate_similarity_score(&column1, &column2);
+        println!("Similarity: {}", similarity_score);
+    }
+}
 
-    let similarity_score = calculate_similarity_score(&column1, &column2);
-    println!("Similarity: {}", similarity_score);
+fn stream(args: &Args, options: &StreamOptions) -> Result<Totals, LoadError> {
+    let path = match &args.inputs[..] {
+        [] => PathBuf::from("input.txt"),
+        [path] => path.clone(),
+        _ => {
+            return Err(LoadError::Io {
+                name: "--input".to_string(),
+                error: io::Error::new(io::ErrorKind::InvalidInput, "streaming reads one input"),
+            })
+        }
+    };
+    if path.as_os_str() == "-" {
+        return summarize(io::stdin().lock(), "stdin", options);
+    }
+    let name = path.display().to_string();
+    let file = File::open(&path).map_err(|error| LoadError::Io {
+        name: name.clone(),
+        error,
+    })?;
+    summarize(BufReader::new(file), &name, options)
 }
 
-fn calculate_total_distance(left: &[i32], right: &[i32]) -> Vec<i32> {
-    let mut results: Vec<i32> = Vec::new();
+fn print_metrics(left: &[i32], right: &[i32], top: usize) {
+    let overlap = metrics::overlap(left, right);
+    println!(
+        "Distinct IDs: {} shared, {} only on the left, {} only on the right",
+        overlap.shared, overlap.only_left, overlap.only_right
+    );
+    println!("Jaccard index: {:.4}", overlap.jaccard());
+    let correlation = |value: Option<f64>| match value {
+        Some(value) => format!("{:.4}", value),
+        None => "undefined".to_string(),
+    };
+    println!(
+        "Spearman's rho: {}",
+        correlation(metrics::spearman(left, right))
+    );
+    println!(
+        "Kendall's tau-b: {}",
+        correlation(metrics::kendall(left, right))
+    );
 
-    for (i, j) in left.iter().zip(right.iter()) {
-        let distance = i - j;
-        results.push(distance.abs());
+    println!("Most common shared IDs:");
+    for SharedId {
+        id,
+        left_count,
+        right_count,
+    } in metrics::most_common_shared(left, right, top)
+    {
+        println!("  {:>10}  {} left, {} right", id, left_count, right_count);
+    }
+    println!("Largest contributions to the similarity score:");
+    for shared in metrics::similarity_contributions(left, right)
+        .iter()
+        .take(top)
+    {
+        println!("  {:>10}  {}", shared.id, shared.contribution());
     }
-    results
 }
 
-fn calculate_similarity_score(left: &[i32], right: &[i32]) -> i32 {
-    let mut frequencies: HashMap<i32, i32> = HashMap::new();
-    let mut appearances: Vec<i32> = Vec::new();
-    for &value in right {
-        *frequencies.entry(value).or_insert(0) += 1;
+fn report(skipped: &[ParseError]) {
+    if skipped.is_empty() {
+        return;
     }
-
-    for &value in left {
-        if let Some(&count) = frequencies.get(&value) {
-            appearances.push(count * value)
-        }
+    eprintln!("Skipped {} lines:", skipped.len());
+    for error in skipped {
+        eprintln!("  {}", error);
     }
-    appearances.iter().sum()
 }
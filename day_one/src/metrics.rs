@@ -0,0 +1,270 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+// These take the columns in the input's row order, which only matters for the
+// rank correlations; everything else treats the columns as multisets.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap {
+    // Counts of distinct IDs.
+    pub shared: usize,
+    pub only_left: usize,
+    pub only_right: usize,
+}
+
+impl Overlap {
+    pub fn union(&self) -> usize {
+        self.shared + self.only_left + self.only_right
+    }
+
+    // Shared IDs as a fraction of all IDs, 1 for two empty lists.
+    pub fn jaccard(&self) -> f64 {
+        match self.union() {
+            0 => 1.0,
+            union => self.shared as f64 / union as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedId {
+    pub id: i32,
+    pub left_count: usize,
+    pub right_count: usize,
+}
+
+impl SharedId {
+    // This ID's part of the similarity score.
+    pub fn contribution(&self) -> i64 {
+        self.id as i64 * self.left_count as i64 * self.right_count as i64
+    }
+}
+
+fn counts(ids: &[i32]) -> HashMap<i32, usize> {
+    let mut counts = HashMap::new();
+    for &id in ids {
+        *counts.entry(id).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub fn overlap(left: &[i32], right: &[i32]) -> Overlap {
+    let left = counts(left);
+    let right = counts(right);
+    let shared = left.keys().filter(|id| right.contains_key(id)).count();
+    Overlap {
+        shared,
+        only_left: left.len() - shared,
+        only_right: right.len() - shared,
+    }
+}
+
+// Every ID found in both lists, in no particular order.
+pub fn shared_ids(left: &[i32], right: &[i32]) -> Vec<SharedId> {
+    let right = counts(right);
+    counts(left)
+        .into_iter()
+        .filter_map(|(id, left_count)| {
+            right.get(&id).map(|&right_count| SharedId {
+                id,
+                left_count,
+                right_count,
+            })
+        })
+        .collect()
+}
+
+// The `k` shared IDs that appear most often across both lists.
+pub fn most_common_shared(left: &[i32], right: &[i32], k: usize) -> Vec<SharedId> {
+    let mut shared = shared_ids(left, right);
+    shared.sort_by_key(|s| (Reverse(s.left_count + s.right_count), s.id));
+    shared.truncate(k);
+    shared
+}
+
+// Each shared ID's part of the similarity score, largest first. They add up to
+// `calculate_similarity_score`.
+pub fn similarity_contributions(left: &[i32], right: &[i32]) -> Vec<SharedId> {
+    let mut shared = shared_ids(left, right);
+    shared.sort_by_key(|s| (Reverse(s.contribution()), s.id));
+    shared
+}
+
+// Ranks starting from 1, with tied values sharing the mean of their ranks.
+fn ranks(values: &[i32]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by_key(|&i| values[i]);
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len() as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y) {
+        covariance += (a - mean_x) * (b - mean_y);
+        variance_x += (a - mean_x).powi(2);
+        variance_y += (b - mean_y).powi(2);
+    }
+    let denominator = (variance_x * variance_y).sqrt();
+    (denominator > 0.0).then(|| covariance / denominator)
+}
+
+// Spearman's rho between the columns row by row, or None when there are fewer
+// than two rows or a column is constant.
+pub fn spearman(left: &[i32], right: &[i32]) -> Option<f64> {
+    let n = left.len().min(right.len());
+    if n < 2 {
+        return None;
+    }
+    pearson(&ranks(&left[..n]), &ranks(&right[..n]))
+}
+
+// Kendall's tau-b between the columns row by row, using Knight's O(n log n)
+// algorithm so that it copes with long lists. None when there are fewer than
+// two rows or a column is constant.
+pub fn kendall(left: &[i32], right: &[i32]) -> Option<f64> {
+    let mut pairs: Vec<(i32, i32)> = left.iter().copied().zip(right.iter().copied()).collect();
+    if pairs.len() < 2 {
+        return None;
+    }
+    pairs.sort_unstable();
+    let total = tied_pairs([pairs.len()]);
+    let left_ties = tied_pairs(runs(&pairs, |p| p.0));
+    let joint_ties = tied_pairs(runs(&pairs, |p| *p));
+
+    let mut ys: Vec<i32> = pairs.iter().map(|p| p.1).collect();
+    let swaps = merge_sort_swaps(&mut ys);
+    let right_ties = tied_pairs(runs(&ys, |y| *y));
+
+    let numerator = total as f64 - left_ties as f64 - right_ties as f64 + joint_ties as f64
+        - 2.0 * swaps as f64;
+    let denominator = ((total - left_ties) as f64 * (total - right_ties) as f64).sqrt();
+    (denominator > 0.0).then(|| numerator / denominator)
+}
+
+// How many pairs can be drawn from within each run.
+fn tied_pairs(runs: impl IntoIterator<Item = usize>) -> u64 {
+    runs.into_iter()
+        .map(|run| run as u64 * (run as u64 - 1) / 2)
+        .sum()
+}
+
+// Lengths of the runs of equal keys in a sorted slice.
+fn runs<T, K: PartialEq>(sorted: &[T], key: impl Fn(&T) -> K) -> Vec<usize> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=sorted.len() {
+        if i == sorted.len() || key(&sorted[i]) != key(&sorted[start]) {
+            runs.push(i - start);
+            start = i;
+        }
+    }
+    runs
+}
+
+// Sorts `values`, returning how many swaps of adjacent out-of-order elements
+// an insertion sort would have made.
+fn merge_sort_swaps(values: &mut [i32]) -> u64 {
+    if values.len() < 2 {
+        return 0;
+    }
+    let middle = values.len() / 2;
+    let mut swaps =
+        merge_sort_swaps(&mut values[..middle]) + merge_sort_swaps(&mut values[middle..]);
+    let mut merged = Vec::with_capacity(values.len());
+    let (mut i, mut j) = (0, middle);
+    while i < middle && j < values.len() {
+        if values[j] < values[i] {
+            swaps += (middle - i) as u64;
+            merged.push(values[j]);
+            j += 1;
+        } else {
+            merged.push(values[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&values[i..middle]);
+    merged.extend_from_slice(&values[j..]);
+    values.copy_from_slice(&merged);
+    swaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_similarity_score;
+
+    const LEFT: [i32; 6] = [3, 4, 2, 1, 3, 3];
+    const RIGHT: [i32; 6] = [4, 3, 5, 3, 9, 3];
+
+    fn close(a: Option<f64>, b: f64) -> bool {
+        a.is_some_and(|a| (a - b).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_overlap() {
+        let overlap = overlap(&LEFT, &RIGHT);
+        assert_eq!(
+            overlap,
+            Overlap {
+                shared: 2,
+                only_left: 2,
+                only_right: 2
+            }
+        );
+        assert_eq!(overlap.union(), 6);
+        assert!(close(Some(overlap.jaccard()), 1.0 / 3.0));
+    }
+
+    #[test]
+    fn test_shared_ids() {
+        let contributions = similarity_contributions(&LEFT, &RIGHT);
+        let scores: Vec<(i32, i64)> = contributions
+            .iter()
+            .map(|s| (s.id, s.contribution()))
+            .collect();
+        assert_eq!(scores, [(3, 27), (4, 4)]);
+        let total: i64 = contributions.iter().map(SharedId::contribution).sum();
+        assert_eq!(total, calculate_similarity_score(&LEFT, &RIGHT));
+
+        let top = most_common_shared(&LEFT, &RIGHT, 1);
+        assert_eq!(
+            top,
+            [SharedId {
+                id: 3,
+                left_count: 3,
+                right_count: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn test_rank_correlations() {
+        assert!(close(spearman(&[1, 2, 3], &[10, 20, 30]), 1.0));
+        assert!(close(kendall(&[1, 2, 3], &[30, 20, 10]), -1.0));
+        assert!(close(spearman(&[1, 2, 3, 4], &[1, 3, 2, 4]), 0.8));
+        assert!(close(kendall(&[1, 2, 3, 4], &[1, 3, 2, 4]), 4.0 / 6.0));
+        // Two concordant pairs, three discordant and one tied on the left.
+        assert!(close(
+            kendall(&[1, 1, 2, 3], &[1, 2, 3, 0]),
+            -1.0 / 30f64.sqrt()
+        ));
+        assert_eq!(spearman(&[1], &[2]), None);
+        assert_eq!(kendall(&[5, 5, 5], &[1, 2, 3]), None);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{distance, parse_pair, Mode};
+use aoc_common::parse::Line;
+use aoc_common::LoadError;
+use aoc_common::ParseError;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs::{remove_file, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::vec;
+
+static RUNS_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+pub struct StreamOptions {
+    // How many IDs of each column are held in memory before they are sorted
+    // and spilled to a file in `temp_dir`.
+    pub chunk_size: usize,
+    pub temp_dir: PathBuf,
+    pub mode: Mode,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions {
+            chunk_size: 1 << 22,
+            temp_dir: std::env::temp_dir(),
+            mode: Mode::Strict,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Totals {
+    pub pairs: u64,
+    pub distance: u64,
+    pub similarity: i64,
+    // Lines left out in lenient mode, and why.
+    pub skipped: Vec<ParseError>,
+}
+
+// Both answers for a list too big to hold in memory. Each column is sorted
+// with an external merge sort, so memory use is bounded by the chunk size,
+// apart from the frequency maps behind the similarity score, which grow with
+// the number of distinct IDs rather than the number of pairs.
+pub fn summarize(
+    reader: impl BufRead,
+    name: &str,
+    options: &StreamOptions,
+) -> Result<Totals, LoadError> {
+    let spill_error = |error| LoadError::Io {
+        name: options.temp_dir.display().to_string(),
+        error,
+    };
+    let mut left = Column::new(options);
+    let mut right = Column::new(options);
+    let mut left_counts: HashMap<i32, i64> = HashMap::new();
+    let mut right_counts: HashMap<i32, i64> = HashMap::new();
+    let mut totals = Totals::default();
+
+    for (i, text) in reader.lines().enumerate() {
+        let text = text.map_err(|error| LoadError::Io {
+            name: name.to_string(),
+            error,
+        })?;
+        let line = Line {
+            number: i + 1,
+            text: &text,
+        };
+        let (first, second) = match parse_pair(&line) {
+            Ok(Some(pair)) => pair,
+            Ok(None) => continue,
+            Err(e) => {
+                let e = e.in_file(name);
+                options.mode.handle(e, &mut totals.skipped)?;
+                continue;
+            }
+        };
+        left.push(first).map_err(spill_error)?;
+        right.push(second).map_err(spill_error)?;
+        *left_counts.entry(first).or_insert(0) += 1;
+        *right_counts.entry(second).or_insert(0) += 1;
+        totals.pairs += 1;
+    }
+
+    totals.similarity = left_counts
+        .iter()
+        .filter_map(|(&id, &count)| {
+            right_counts
+                .get(&id)
+                .map(|&other| id as i64 * count * other)
+        })
+        .sum();
+    let left = left.into_sorted().map_err(spill_error)?;
+    let right = right.into_sorted().map_err(spill_error)?;
+    for (first, second) in left.zip(right) {
+        totals.distance += distance(first.map_err(spill_error)?, second.map_err(spill_error)?);
+    }
+    Ok(totals)
+}
+
+// One column of IDs, sorted a chunk at a time.
+struct Column<'a> {
+    options: &'a StreamOptions,
+    chunk: Vec<i32>,
+    runs: Vec<Run>,
+}
+
+impl<'a> Column<'a> {
+    fn new(options: &'a StreamOptions) -> Self {
+        Column {
+            options,
+            chunk: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, id: i32) -> io::Result<()> {
+        self.chunk.push(id);
+        if self.chunk.len() >= self.options.chunk_size.max(1) {
+            self.chunk.sort_unstable();
+            self.runs
+                .push(Run::write(&self.options.temp_dir, &self.chunk)?);
+            self.chunk.clear();
+        }
+        Ok(())
+    }
+
+    fn into_sorted(mut self) -> io::Result<Merge> {
+        self.chunk.sort_unstable();
+        let mut sources = vec![Source::Memory(self.chunk.into_iter())];
+        for run in &self.runs {
+            sources.push(Source::File(BufReader::new(File::open(&run.path)?)));
+        }
+        Merge::new(sources, self.runs)
+    }
+}
+
+// A sorted chunk spilled to disk as little-endian IDs, removed when dropped.
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    fn write(dir: &Path, ids: &[i32]) -> io::Result<Run> {
+        let number = RUNS_CREATED.fetch_add(1, Ordering::Relaxed);
+        let run = Run {
+            path: dir.join(format!("day_one-{}-{}.run", process::id(), number)),
+        };
+        let mut writer = BufWriter::new(File::create(&run.path)?);
+        for id in ids {
+            writer.write_all(&id.to_le_bytes())?;
+        }
+        writer.flush()?;
+        Ok(run)
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = remove_file(&self.path);
+    }
+}
+
+enum Source {
+    Memory(vec::IntoIter<i32>),
+    File(BufReader<File>),
+}
+
+impl Source {
+    fn next(&mut self) -> io::Result<Option<i32>> {
+        match self {
+            Source::Memory(ids) => Ok(ids.next()),
+            Source::File(reader) => {
+                let mut bytes = [0; 4];
+                match reader.read_exact(&mut bytes) {
+                    Ok(()) => Ok(Some(i32::from_le_bytes(bytes))),
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+}
+
+// K-way merge of sorted sources, smallest ID first.
+struct Merge {
+    sources: Vec<Source>,
+    heap: BinaryHeap<Reverse<(i32, usize)>>,
+    // Kept so the files outlive the readers.
+    _runs: Vec<Run>,
+}
+
+impl Merge {
+    fn new(mut sources: Vec<Source>, runs: Vec<Run>) -> io::Result<Merge> {
+        let mut heap = BinaryHeap::new();
+        for (i, source) in sources.iter_mut().enumerate() {
+            if let Some(id) = source.next()? {
+                heap.push(Reverse((id, i)));
+            }
+        }
+        Ok(Merge {
+            sources,
+            heap,
+            _runs: runs,
+        })
+    }
+}
+
+impl Iterator for Merge {
+    type Item = io::Result<i32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((id, i)) = self.heap.pop()?;
+        match self.sources[i].next() {
+            Ok(Some(next)) => self.heap.push(Reverse((next, i))),
+            Ok(None) => {}
+            Err(e) => return Some(Err(e)),
+        }
+        Some(Ok(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_similarity_score, calculate_total_distance, parse_columns};
+    use std::fs::{create_dir_all, read_dir, remove_dir};
+
+    #[test]
+    fn test_matches_in_memory_answers() {
+        let input: String = (0..1000)
+            .map(|i| format!("{}   {}\n", (i * 7919) % 1000, (i * 104729) % 997))
+            .collect();
+        let (left, right) = parse_columns(&input).unwrap();
+
+        let temp_dir = std::env::temp_dir().join(format!("day_one-test-{}", process::id()));
+        create_dir_all(&temp_dir).unwrap();
+        let options = StreamOptions {
+            chunk_size: 64,
+            temp_dir: temp_dir.clone(),
+            mode: Mode::Strict,
+        };
+        let totals = summarize(input.as_bytes(), "input", &options).unwrap();
+        assert_eq!(totals.pairs, 1000);
+        assert_eq!(totals.distance, calculate_total_distance(&left, &right));
+        assert_eq!(totals.similarity, calculate_similarity_score(&left, &right));
+
+        assert_eq!(read_dir(&temp_dir).unwrap().count(), 0);
+        remove_dir(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        let error = summarize("1 2\n3\n".as_bytes(), "ids.txt", &StreamOptions::default());
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "ids.txt:2:2: expected two location IDs, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_lenient_streaming() {
+        let options = StreamOptions {
+            mode: Mode::Lenient,
+            ..StreamOptions::default()
+        };
+        let totals = summarize("1 2\n3\n2 1\n".as_bytes(), "ids.txt", &options).unwrap();
+        assert_eq!(totals.pairs, 2);
+        assert_eq!(totals.distance, 0);
+        assert_eq!(totals.skipped.len(), 1);
+        assert_eq!(totals.skipped[0].file.as_deref(), Some("ids.txt"));
+    }
+}
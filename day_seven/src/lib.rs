@@ -0,0 +1,189 @@
+pub mod operator;
+
+use aoc_common::{parse, ParseError, Solution};
+use operator::{Inverse, Operator};
+use rayon::prelude::*;
+
+#[derive(Debug, Clone)]
+pub struct Equation {
+    pub answer: u64,
+    pub numbers: Vec<u64>,
+}
+
+pub fn parse_equations(input: &str) -> Result<Vec<Equation>, ParseError> {
+    parse::lines(input)
+        .map(|line| {
+            let (answer, numbers) = line.split_once(line.text, ":")?;
+            let answer = line.parse(answer.trim(), "a test value")?;
+            let numbers = numbers
+                .split_whitespace()
+                .map(|x| line.parse(x, "a number"))
+                .collect::<Result<Vec<u64>, _>>()?;
+            if numbers.is_empty() {
+                return Err(line.error(&line.text[line.text.len()..], "at least one number"));
+            }
+            Ok(Equation { answer, numbers })
+        })
+        .collect()
+}
+
+// Works back from the answer, undoing the last operator on each remaining
+// number, so a branch ends as soon as no operator can produce what's needed.
+// Returns the operators that make the equation true, first to last.
+pub fn solve_with<'a>(
+    equation: &Equation,
+    operators: &[&'a dyn Operator],
+) -> Option<Vec<&'a dyn Operator>> {
+    if equation.numbers.is_empty() {
+        return None;
+    }
+    let mut ops = Vec::with_capacity(equation.numbers.len() - 1);
+    solve_back(equation.answer, &equation.numbers, operators, &mut ops).then_some(ops)
+}
+
+fn solve_back<'a>(
+    target: u64,
+    numbers: &[u64],
+    operators: &[&'a dyn Operator],
+    ops: &mut Vec<&'a dyn Operator>,
+) -> bool {
+    let Some((&last, rest)) = numbers.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return last == target;
+    }
+    for &op in operators {
+        let found = match op.inverse(target, last) {
+            Inverse::Left(left) => solve_back(left, rest, operators, ops),
+            Inverse::Any => solve_any(rest[0], &rest[1..], operators, ops),
+            Inverse::Impossible => false,
+        };
+        if found {
+            ops.push(op);
+            return true;
+        }
+    }
+    false
+}
+
+// Any operators at all that take `value` through the rest of `numbers`
+// without overflowing.
+fn solve_any<'a>(
+    value: u64,
+    numbers: &[u64],
+    operators: &[&'a dyn Operator],
+    ops: &mut Vec<&'a dyn Operator>,
+) -> bool {
+    let Some((&next, rest)) = numbers.split_first() else {
+        return true;
+    };
+    for &op in operators {
+        if let Some(value) = op.apply(value, next) {
+            ops.push(op);
+            if solve_any(value, rest, operators, ops) {
+                return true;
+            }
+            ops.pop();
+        }
+    }
+    false
+}
+
+pub fn solve_equation(equation: &Equation, operators: &[&dyn Operator]) -> Option<(u64, String)> {
+    let ops = solve_with(equation, operators)?;
+    let mut expression = equation.numbers[0].to_string();
+    for (op, number) in ops.iter().zip(&equation.numbers[1..]) {
+        expression.push(op.symbol());
+        expression.push_str(&number.to_string());
+    }
+    Some((equation.answer, expression))
+}
+
+pub struct DaySeven;
+
+impl DaySeven {
+    fn total(equations: &[Equation], operators: &[&dyn Operator]) -> u64 {
+        equations
+            .par_iter()
+            .filter(|equation| solve_with(equation, operators).is_some())
+            .map(|equation| equation.answer)
+            .sum()
+    }
+}
+
+impl Solution for DaySeven {
+    type Input = Vec<Equation>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_equations(input)
+    }
+
+    fn part_one(equations: &Self::Input) -> Self::PartOne {
+        DaySeven::total(equations, &operator::PART_ONE)
+    }
+
+    fn part_two(equations: &Self::Input) -> Self::PartTwo {
+        DaySeven::total(equations, &operator::PART_TWO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::test_util::compare;
+    use operator::ALL;
+
+    // Every combination of operators, as the puzzle describes.
+    fn brute_force(equation: &Equation, operators: &[&dyn Operator]) -> bool {
+        let gaps = equation.numbers.len() - 1;
+        (0..operators.len().pow(gaps as u32)).any(|mut combination| {
+            let mut value = Some(equation.numbers[0]);
+            for &number in &equation.numbers[1..] {
+                let op = operators[combination % operators.len()];
+                combination /= operators.len();
+                value = value.and_then(|value| op.apply(value, number));
+            }
+            value == Some(equation.answer)
+        })
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for operators in [&ALL[..2], &ALL[..3], &ALL[..]] {
+            let solved = compare(
+                11,
+                400,
+                |random| {
+                    let count = 1 + random.below(5);
+                    let numbers: Vec<u64> = (0..count).map(|_| random.below(12) as u64).collect();
+                    // Answers made from the numbers, so that some of them work out.
+                    let mut answer = numbers[0];
+                    for &number in &numbers[1..] {
+                        let op = operators[random.below(operators.len() as u32) as usize];
+                        answer = op.apply(answer, number).unwrap_or(answer);
+                    }
+                    Equation { answer, numbers }
+                },
+                // What the operators found come to, which should be the answer.
+                |equation| {
+                    let ops = solve_with(equation, operators)?;
+                    let mut value = Some(equation.numbers[0]);
+                    for (op, &number) in ops.iter().zip(&equation.numbers[1..]) {
+                        value = value.and_then(|value| op.apply(value, number));
+                    }
+                    Some(value)
+                },
+                |equation| brute_force(equation, operators).then_some(Some(equation.answer)),
+            );
+            assert!(solved.iter().flatten().count() > 100);
+        }
+        let empty = Equation {
+            answer: 0,
+            numbers: Vec::new(),
+        };
+        assert!(solve_with(&empty, &ALL).is_none());
+    }
+}
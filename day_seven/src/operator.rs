@@ -0,0 +1,208 @@
+// Operators are evaluated left to right on unsigned numbers, so anything that
+// would overflow or go negative has no value and can't be part of a solution.
+pub trait Operator: Sync {
+    fn symbol(&self) -> char;
+    fn apply(&self, left: u64, right: u64) -> Option<u64>;
+    // What `left` would have to be for `left op right` to come to `result`.
+    fn inverse(&self, result: u64, right: u64) -> Inverse;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inverse {
+    Left(u64),
+    // Every left works, as for `x * 0 == 0`.
+    Any,
+    Impossible,
+}
+
+impl From<Option<u64>> for Inverse {
+    fn from(left: Option<u64>) -> Self {
+        left.map_or(Inverse::Impossible, Inverse::Left)
+    }
+}
+
+pub struct Add;
+pub struct Multiply;
+pub struct Concatenate;
+pub struct Subtract;
+pub struct Power;
+
+pub const PART_ONE: [&dyn Operator; 2] = [&Add, &Multiply];
+pub const PART_TWO: [&dyn Operator; 3] = [&Add, &Multiply, &Concatenate];
+pub const ALL: [&dyn Operator; 5] = [&Add, &Multiply, &Concatenate, &Subtract, &Power];
+
+// The operators with the given symbols, such as "+*|".
+pub fn parse_operators(symbols: &str) -> Result<Vec<&'static dyn Operator>, String> {
+    symbols
+        .chars()
+        .map(|c| {
+            ALL.into_iter()
+                .find(|op| op.symbol() == c)
+                .ok_or_else(|| format!("unknown operator {:?}, expected one of + * | - ^", c))
+        })
+        .collect()
+}
+
+impl Operator for Add {
+    fn symbol(&self) -> char {
+        '+'
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_add(right)
+    }
+
+    fn inverse(&self, result: u64, right: u64) -> Inverse {
+        result.checked_sub(right).into()
+    }
+}
+
+impl Operator for Multiply {
+    fn symbol(&self) -> char {
+        '*'
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_mul(right)
+    }
+
+    fn inverse(&self, result: u64, right: u64) -> Inverse {
+        match (result, right) {
+            (0, 0) => Inverse::Any,
+            (_, 0) => Inverse::Impossible,
+            _ if result.is_multiple_of(right) => Inverse::Left(result / right),
+            _ => Inverse::Impossible,
+        }
+    }
+}
+
+// Joins the digits, so 12 | 345 is 12345.
+impl Operator for Concatenate {
+    fn symbol(&self) -> char {
+        '|'
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_mul(Concatenate::shift(right)?)?
+            .checked_add(right)
+    }
+
+    fn inverse(&self, result: u64, right: u64) -> Inverse {
+        match Concatenate::shift(right) {
+            Some(shift) if result % shift == right => Inverse::Left(result / shift),
+            _ => Inverse::Impossible,
+        }
+    }
+}
+
+impl Concatenate {
+    // The power of ten that makes room for `right`'s digits.
+    fn shift(right: u64) -> Option<u64> {
+        10_u64.checked_pow(right.checked_ilog10().unwrap_or(0) + 1)
+    }
+}
+
+impl Operator for Subtract {
+    fn symbol(&self) -> char {
+        '-'
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_sub(right)
+    }
+
+    fn inverse(&self, result: u64, right: u64) -> Inverse {
+        result.checked_add(right).into()
+    }
+}
+
+impl Operator for Power {
+    fn symbol(&self) -> char {
+        '^'
+    }
+
+    fn apply(&self, left: u64, right: u64) -> Option<u64> {
+        left.checked_pow(u32::try_from(right).ok()?)
+    }
+
+    // The exact `right`th root, found by binary search so that large bases
+    // don't lose precision.
+    fn inverse(&self, result: u64, right: u64) -> Inverse {
+        match (result, right) {
+            (1, 0) => return Inverse::Any,
+            (_, 0) => return Inverse::Impossible,
+            (_, 1) => return Inverse::Left(result),
+            _ => {}
+        }
+        let Ok(exponent) = u32::try_from(right) else {
+            return Inverse::Impossible;
+        };
+        let (mut low, mut high) = (0, result);
+        while low < high {
+            let middle = low + (high - low).div_ceil(2);
+            match middle.checked_pow(exponent) {
+                Some(power) if power <= result => low = middle,
+                _ => high = middle - 1,
+            }
+        }
+        match low.pow(exponent) == result {
+            true => Inverse::Left(low),
+            false => Inverse::Impossible,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverses_undo_apply() {
+        let numbers = [
+            0,
+            1,
+            2,
+            3,
+            7,
+            10,
+            12,
+            99,
+            100,
+            1000,
+            123456,
+            4_294_967_295,
+            1_000_000_000_000_000_007,
+            u64::MAX,
+        ];
+        for op in ALL {
+            for left in numbers {
+                for right in numbers {
+                    let Some(result) = op.apply(left, right) else {
+                        continue;
+                    };
+                    match op.inverse(result, right) {
+                        Inverse::Left(inverse) => assert_eq!(
+                            op.apply(inverse, right),
+                            Some(result),
+                            "{} {} {}",
+                            left,
+                            op.symbol(),
+                            right
+                        ),
+                        Inverse::Any => {}
+                        Inverse::Impossible => {
+                            panic!("{} {} {} has no inverse", left, op.symbol(), right)
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(Power.inverse(1 << 40, 4), Inverse::Left(1024));
+        assert_eq!(Power.inverse(1000, 2), Inverse::Impossible);
+        assert_eq!(Power.inverse(u64::MAX, 1), Inverse::Left(u64::MAX));
+        assert_eq!(Power.inverse(1 << 63, 63), Inverse::Left(2));
+        assert_eq!(Concatenate.inverse(12345, 45), Inverse::Left(123));
+        assert_eq!(Concatenate.inverse(12345, 4), Inverse::Impossible);
+        assert!(parse_operators("+*x").is_err());
+    }
+}
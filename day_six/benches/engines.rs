@@ -0,0 +1,64 @@
+// Times both engines for part two on the puzzle input and on larger made-up
+// labs: `cargo bench -p day_six`.
+use aoc_common::test_util::Random;
+use day_six::{Engine, LabMap};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 9;
+
+// A square lab with the guard in the middle and about one cell in 20 blocked,
+// like the puzzle input.
+fn generated(size: usize, seed: u32) -> String {
+    let mut random = Random::new(seed);
+    let mut input = String::new();
+    for y in 0..size {
+        for x in 0..size {
+            input.push(match (x, y) {
+                _ if (x, y) == (size / 2, size / 2) => '^',
+                _ if random.one_in(20) => '#',
+                _ => '.',
+            });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+fn median(lab_map: &LabMap, engine: Engine) -> Duration {
+    let mut timings: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(lab_map.loop_obstructions_with(engine));
+            start.elapsed()
+        })
+        .collect();
+    timings.sort();
+    timings[RUNS / 2]
+}
+
+fn main() {
+    let mut labs = vec![(
+        "input.txt".to_string(),
+        include_str!("../input.txt").to_string(),
+    )];
+    for (size, seed) in [(400, 1), (400, 2), (600, 3)] {
+        labs.push((format!("{0}x{0} #{1}", size, seed), generated(size, seed)));
+    }
+    println!(
+        "{:<12} {:>10} {:>10} {:>10}",
+        "lab", "step", "jump", "speedup"
+    );
+    for (name, input) in labs {
+        let lab_map = LabMap::parse(&input).unwrap();
+        let step = median(&lab_map, Engine::Step);
+        let jump = median(&lab_map, Engine::Jump);
+        println!(
+            "{:<12} {:>10.2?} {:>10.2?} {:>9.1}x",
+            name,
+            step,
+            jump,
+            step.as_secs_f64() / jump.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{bit, LabMap};
+use grid::{Direction, Grid, Point};
+
+// How far the guard walks from every cell in every direction before it has to
+// turn, so that a walk takes one lookup per turn instead of one per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpTable {
+    obstacles: Grid<bool>,
+    // Steps to the last free cell before the next obstacle, or `OFF` if the
+    // guard walks off the map. Indexed by direction, and `OFF` on obstacles.
+    distances: Grid<[u32; 4]>,
+}
+
+const OFF: u32 = u32::MAX;
+
+impl JumpTable {
+    // Each cell's distances follow from its neighbour's, so one sweep down the
+    // map covers north and west, and one back up covers south and east.
+    pub fn new(lab_map: &LabMap) -> Self {
+        let obstacles = lab_map.obstacles.clone();
+        let distances = Grid::new(obstacles.width(), obstacles.height(), [OFF; 4]);
+        let mut table = JumpTable {
+            obstacles,
+            distances,
+        };
+        let points: Vec<Point> = table.obstacles.points().collect();
+        for &point in &points {
+            table.extend(point, Direction::North);
+            table.extend(point, Direction::West);
+        }
+        for &point in points.iter().rev() {
+            table.extend(point, Direction::South);
+            table.extend(point, Direction::East);
+        }
+        table
+    }
+
+    // Works out `point`'s distance heading `direction` from the next cell's.
+    fn extend(&mut self, point: Point, direction: Direction) {
+        let next = point + direction.delta();
+        let distance = match self.obstacles.get(next) {
+            _ if self.obstacles[point] => OFF,
+            None => OFF,
+            Some(true) => 0,
+            Some(false) => match self.distances[next][direction as usize] {
+                OFF => OFF,
+                distance => distance + 1,
+            },
+        };
+        self.distances[point][direction as usize] = distance;
+    }
+
+    // Recomputes the distances heading `direction` along the row or column
+    // through `point`, working back from the edge the guard would walk off.
+    fn fill(&mut self, point: Point, direction: Direction) {
+        let delta = direction.delta();
+        let mut cell = point;
+        while self.obstacles.contains(cell + delta) {
+            cell += delta;
+        }
+        while self.obstacles.contains(cell) {
+            self.extend(cell, direction);
+            cell -= delta;
+        }
+    }
+
+    // Only the row and column through `point` change, so this is O(width +
+    // height).
+    pub fn set_obstacle(&mut self, point: Point, obstacle: bool) {
+        if !self.obstacles.set(point, obstacle) {
+            return;
+        }
+        for direction in Direction::ALL {
+            self.fill(point, direction);
+        }
+    }
+
+    pub fn scratch(&self) -> Scratch {
+        Scratch {
+            turns: Grid::new(self.obstacles.width(), self.obstacles.height(), 0),
+            touched: Vec::new(),
+        }
+    }
+
+    // Where the guard stops heading `direction` from `pos`, with one more
+    // obstacle that isn't in the table. The extra obstacle only matters if
+    // it's ahead of the guard and no further than where it would stop anyway.
+    fn stop(&self, pos: Point, direction: Direction, obstacle: Point) -> Option<Point> {
+        let delta = direction.delta();
+        let ahead = obstacle - pos;
+        let to_obstacle = ahead.x * delta.x + ahead.y * delta.y;
+        let distance = self.distances[pos][direction as usize];
+        if to_obstacle > 0
+            && ahead == delta * to_obstacle
+            && to_obstacle as u32 <= distance.saturating_add(1)
+        {
+            return Some(obstacle - delta);
+        }
+        match distance {
+            OFF => None,
+            distance => Some(pos + delta * distance as i32),
+        }
+    }
+
+    // Whether a guard at `pos` facing `direction` walks in a loop for ever
+    // with one more obstacle at `obstacle`.
+    pub fn loops_from(
+        &self,
+        mut pos: Point,
+        mut direction: Direction,
+        obstacle: Point,
+        scratch: &mut Scratch,
+    ) -> bool {
+        let mut loops = false;
+        while let Some(stop) = self.stop(pos, direction, obstacle) {
+            let turns = &mut scratch.turns[stop];
+            if *turns & bit(direction) != 0 {
+                loops = true;
+                break;
+            }
+            if *turns == 0 {
+                scratch.touched.push(stop);
+            }
+            *turns |= bit(direction);
+            pos = stop;
+            direction = direction.turn_right();
+        }
+        for point in scratch.touched.drain(..) {
+            scratch.turns[point] = 0;
+        }
+        loops
+    }
+
+    // `LabMap::loops_with`, looked up in the table.
+    pub fn loops_with(&self, lab_map: &LabMap, obstacle: Point, scratch: &mut Scratch) -> bool {
+        self.loops_from(
+            lab_map.guard_pos,
+            lab_map.guard_direction,
+            obstacle,
+            scratch,
+        )
+    }
+}
+
+// The turns a walk has made, kept between walks so that each one only has to
+// clear the cells it used.
+pub struct Scratch {
+    turns: Grid<u8>,
+    touched: Vec<Point>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Engine;
+    use aoc_common::test_util::compare;
+
+    #[test]
+    fn test_incremental_updates() {
+        let lab_map = LabMap::parse(include_str!("../example_1.txt")).unwrap();
+        let fresh = JumpTable::new(&lab_map);
+        assert_eq!(
+            fresh.distances[Point::new(4, 6)][Direction::North as usize],
+            5
+        );
+        assert_eq!(
+            fresh.distances[Point::new(4, 6)][Direction::South as usize],
+            OFF
+        );
+
+        let mut table = fresh.clone();
+        table.set_obstacle(Point::new(3, 6), true);
+        let mut blocked = lab_map.clone();
+        blocked.obstacles[Point::new(3, 6)] = true;
+        assert_eq!(table, JumpTable::new(&blocked));
+        table.set_obstacle(Point::new(3, 6), false);
+        assert_eq!(table, fresh);
+    }
+
+    #[test]
+    fn test_engines_agree() {
+        let obstructions = |engine| {
+            move |input: &String| LabMap::parse(input).unwrap().loop_obstructions_with(engine)
+        };
+        compare(
+            42,
+            20,
+            |random| {
+                let mut input = String::new();
+                for y in 0..24 {
+                    for x in 0..24 {
+                        input.push(match (x, y) {
+                            (12, 12) => '^',
+                            _ if random.one_in(9) => '#',
+                            _ => '.',
+                        });
+                    }
+                    input.push('\n');
+                }
+                input
+            },
+            obstructions(Engine::Jump),
+            obstructions(Engine::Step),
+        );
+    }
+}
@@ -0,0 +1,269 @@
+pub mod jump;
+pub mod replay;
+pub mod trace;
+
+use aoc_common::{input, InputSource, LoadError, ParseError, Solution};
+use clap::ValueEnum;
+use colored::*;
+use grid::{Direction, Grid, Point};
+use jump::JumpTable;
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Exited,
+    Looped,
+}
+
+// How to walk the guard when trying out obstacles. Both give the same results;
+// jumping precomputes how far the guard walks in each direction from each cell,
+// which pays off when the guard's path is long but costs more than it saves on
+// short ones (see `cargo bench`), so stepping is the default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Engine {
+    #[default]
+    Step,
+    Jump,
+}
+
+#[derive(Clone)]
+pub struct LabMap {
+    obstacles: Grid<bool>,
+    // Steps forward the guard has taken, not counting turns.
+    pub steps: usize,
+    guard_pos: Point,
+    guard_direction: Direction,
+    // A bit for each way the guard has faced on each cell.
+    visited: Grid<u8>,
+    // Whether the guard is facing the way it already has on this cell.
+    looping: bool,
+}
+
+fn bit(direction: Direction) -> u8 {
+    1 << direction as u8
+}
+
+impl LabMap {
+    pub fn load(source: impl InputSource) -> Result<LabMap, LoadError> {
+        input::load(source, LabMap::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<LabMap, ParseError> {
+        let mut guard_pos = None;
+        let obstacles = Grid::try_parse(input, "'.', '#' or '^'", |point, c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            '^' => {
+                guard_pos = Some(point);
+                Some(false)
+            }
+            _ => None,
+        })?;
+        let guard_pos = guard_pos.ok_or_else(|| ParseError::whole_input("a guard '^'", "none"))?;
+
+        let mut visited = Grid::new(obstacles.width(), obstacles.height(), 0);
+        visited[guard_pos] = bit(Direction::North);
+        Ok(LabMap {
+            obstacles,
+            guard_pos,
+            steps: 0,
+            guard_direction: Direction::North,
+            visited,
+            looping: false,
+        })
+    }
+
+    pub fn display(&self, viewport_size: i32) {
+        print!("\x1B[2J\x1B[1;1H");
+        let half_size = viewport_size / 2;
+        let view_start_x = (self.guard_pos.x - half_size).max(0);
+        let view_start_y = (self.guard_pos.y - half_size).max(0);
+        let view_end_x = (self.guard_pos.x + half_size).min(self.obstacles.width() as i32 - 1);
+        let view_end_y = (self.guard_pos.y + half_size).min(self.obstacles.height() as i32 - 1);
+        let direction_symbol = match self.guard_direction {
+            Direction::North => "↑",
+            Direction::East => "→",
+            Direction::South => "↓",
+            Direction::West => "←",
+        };
+        println!(
+            "Viewing: ({}, {}) to ({}, {})",
+            view_start_x, view_start_y, view_end_x, view_end_y
+        );
+        println!("╔{}╗", "═".repeat((view_end_x - view_start_x + 1) as usize));
+        for y in view_start_y..=view_end_y {
+            print!("║");
+            for x in view_start_x..=view_end_x {
+                let point = Point::new(x, y);
+                if self.guard_pos == point {
+                    print!("{}", "G".bright_green().bold());
+                } else if self.obstacles[point] {
+                    print!("{}", "#".bright_red());
+                } else if self.visited[point] != 0 {
+                    print!("{}", ".".bright_yellow().dimmed());
+                } else {
+                    print!("{}", ".".bright_blue().dimmed());
+                }
+            }
+            println!("║");
+        }
+        println!("╚{}╝", "═".repeat((view_end_x - view_start_x + 1) as usize));
+        println!("Guard at: {} facing {}", self.guard_pos, direction_symbol);
+    }
+
+    fn is_valid_position(&self, pos: Point) -> bool {
+        !self.obstacles.get(pos).copied().unwrap_or(false)
+    }
+
+    pub fn step(&mut self) -> bool {
+        let next = self.guard_pos + self.guard_direction.delta();
+        if self.is_valid_position(next) {
+            self.steps += 1;
+            self.guard_pos = next;
+            if !self.obstacles.contains(self.guard_pos) {
+                return false;
+            }
+        } else {
+            self.guard_direction = self.guard_direction.turn_right();
+        }
+        let visited = &mut self.visited[self.guard_pos];
+        self.looping = *visited & bit(self.guard_direction) != 0;
+        *visited |= bit(self.guard_direction);
+        true
+    }
+
+    // Steps the guard until it leaves the map, or until it stands where it has
+    // stood before facing the same way, after which it can only go round the
+    // same loop for ever.
+    pub fn patrol(&mut self) -> Outcome {
+        self.patrol_with(|_| {})
+    }
+
+    // `patrol`, calling `on_step` after every step the guard takes.
+    pub fn patrol_with(&mut self, mut on_step: impl FnMut(&LabMap)) -> Outcome {
+        while self.step() {
+            on_step(self);
+            if self.looping {
+                return Outcome::Looped;
+            }
+        }
+        Outcome::Exited
+    }
+
+    // Whether the guard, from where it is now, gets stuck in a loop with one
+    // more obstacle at `obstacle`. This doesn't record the walk, and only
+    // remembers the places the guard turned: any loop has to turn somewhere.
+    pub fn loops_with(&self, obstacle: Point) -> bool {
+        let (mut pos, mut direction) = (self.guard_pos, self.guard_direction);
+        let mut turns = Grid::new(self.obstacles.width(), self.obstacles.height(), 0u8);
+        loop {
+            let next = pos + direction.delta();
+            if !self.obstacles.contains(next) {
+                return false;
+            }
+            if next == obstacle || self.obstacles[next] {
+                if turns[pos] & bit(direction) != 0 {
+                    return true;
+                }
+                turns[pos] |= bit(direction);
+                direction = direction.turn_right();
+            } else {
+                pos = next;
+            }
+        }
+    }
+
+    // Every place a single new obstacle would trap the guard in a loop. Only
+    // the cells on the guard's path can change where it goes, apart from the
+    // one it starts on, which it would notice being put there.
+    pub fn loop_obstructions(&self) -> Vec<Point> {
+        self.loop_obstructions_with(Engine::default())
+    }
+
+    pub fn loop_obstructions_with(&self, engine: Engine) -> Vec<Point> {
+        let mut walk = self.clone();
+        walk.patrol();
+        let mut candidates: Vec<Point> = walk
+            .visited
+            .iter()
+            .filter(|&(point, &visited)| visited != 0 && point != self.guard_pos)
+            .map(|(point, _)| point)
+            .collect();
+        candidates.sort();
+        match engine {
+            Engine::Step => candidates
+                .into_par_iter()
+                .filter(|&point| self.loops_with(point))
+                .collect(),
+            Engine::Jump => {
+                let table = JumpTable::new(self);
+                candidates
+                    .into_par_iter()
+                    .map_init(
+                        || table.scratch(),
+                        |scratch, point| (point, table.loops_with(self, point, scratch)),
+                    )
+                    .filter(|&(_, loops)| loops)
+                    .map(|(point, _)| point)
+                    .collect()
+            }
+        }
+    }
+
+    pub fn unique_positions_visited(&self) -> usize {
+        self.visited
+            .iter()
+            .filter(|(_, &visited)| visited != 0)
+            .count()
+    }
+}
+
+pub struct DaySix;
+
+impl Solution for DaySix {
+    type Input = LabMap;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        LabMap::parse(input)
+    }
+
+    fn part_one(lab_map: &Self::Input) -> Self::PartOne {
+        let mut lab_map = lab_map.clone();
+        lab_map.patrol();
+        lab_map.unique_positions_visited()
+    }
+
+    fn part_two(lab_map: &Self::Input) -> Self::PartTwo {
+        lab_map.loop_obstructions().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example_1.txt");
+
+    #[test]
+    fn test_loop_detection() {
+        let lab_map = LabMap::parse(EXAMPLE).unwrap();
+        assert_eq!(lab_map.clone().patrol(), Outcome::Exited);
+        assert!(lab_map.loops_with(Point::new(3, 6)));
+        assert!(!lab_map.loops_with(Point::new(1, 1)));
+
+        let mut trapped = LabMap::parse(".#..\n...#\n#^..\n..#.\n").unwrap();
+        assert_eq!(trapped.patrol(), Outcome::Looped);
+    }
+
+    #[test]
+    fn test_loop_obstructions() {
+        let lab_map = LabMap::parse(EXAMPLE).unwrap();
+        let obstructions = lab_map.loop_obstructions();
+        assert_eq!(
+            obstructions,
+            [(1, 8), (3, 6), (3, 8), (6, 7), (7, 7), (7, 9)].map(Point::from)
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use crate::trace::{Action, Trace};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Print, Stylize};
+use crossterm::{cursor, execute, queue, terminal};
+use grid::{Grid, Point};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+const FASTEST: u32 = 10_000;
+const HELP: &str =
+    "space play/pause  ←/→ step  home/end jump  +/- speed  wasd pan  f follow  q quit";
+
+// Where a replay is up to and how it's being viewed, apart from the terminal.
+pub struct Replay<'a> {
+    trace: &'a Trace,
+    // The frame the guard first stood on each cell, so the path so far can be
+    // drawn at any frame without walking it again.
+    first_visits: Grid<usize>,
+    pub frame: usize,
+    pub playing: bool,
+    // Frames a second, and the part of a frame played but not yet shown.
+    pub speed: u32,
+    pending: f64,
+    // Top left corner of the view, or None to keep the guard in the middle.
+    pub view: Option<Point>,
+    // Columns and rows of map on screen.
+    size: Point,
+}
+
+impl<'a> Replay<'a> {
+    pub fn new(trace: &'a Trace) -> Self {
+        let obstacles = &trace.obstacles;
+        let mut first_visits = Grid::new(obstacles.width(), obstacles.height(), usize::MAX);
+        for frame in trace.frames.iter().rev() {
+            first_visits.set(frame.pos, frame.step);
+        }
+        Replay {
+            trace,
+            first_visits,
+            frame: 0,
+            playing: true,
+            speed: 20,
+            pending: 0.0,
+            view: None,
+            size: Point::new(80, 22),
+        }
+    }
+
+    // Fits the view to a terminal, leaving a line above and below the map.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.size = Point::new(width as i32, height.saturating_sub(2) as i32);
+    }
+
+    fn last_frame(&self) -> usize {
+        self.trace.frames.len().saturating_sub(1)
+    }
+
+    fn seek(&mut self, frame: usize) {
+        self.frame = frame.min(self.last_frame());
+        self.pending = 0.0;
+    }
+
+    // Plays on by however many frames `elapsed` is worth, stopping at the end.
+    pub fn advance(&mut self, elapsed: Duration) {
+        if !self.playing {
+            return;
+        }
+        self.pending += elapsed.as_secs_f64() * self.speed as f64;
+        let frames = self.pending as usize;
+        self.pending -= frames as f64;
+        self.frame = (self.frame + frames).min(self.last_frame());
+        if self.frame == self.last_frame() {
+            self.playing = false;
+        }
+    }
+
+    // Returns false when the key asks to quit.
+    pub fn handle(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => {
+                if self.frame == self.last_frame() {
+                    self.seek(0);
+                }
+                self.playing = !self.playing;
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End => {
+                self.playing = false;
+                self.seek(match key {
+                    KeyCode::Left => self.frame.saturating_sub(1),
+                    KeyCode::Right => self.frame + 1,
+                    KeyCode::Home => 0,
+                    _ => self.last_frame(),
+                });
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => self.speed = (self.speed * 2).min(FASTEST),
+            KeyCode::Char('-') => self.speed = (self.speed / 2).max(1),
+            KeyCode::Char('f') => self.view = None,
+            KeyCode::Char(c @ ('w' | 'a' | 's' | 'd')) => {
+                let delta = match c {
+                    'w' => Point::UP,
+                    'a' => Point::LEFT,
+                    's' => Point::DOWN,
+                    _ => Point::RIGHT,
+                };
+                self.view = Some(self.corner() + delta * 4);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn corner(&self) -> Point {
+        match self.view {
+            Some(corner) => corner,
+            None => {
+                self.trace.frames[self.frame].pos - Point::new(self.size.x / 2, self.size.y / 2)
+            }
+        }
+    }
+
+    fn status(&self) -> String {
+        let frame = &self.trace.frames[self.frame];
+        let state = match frame.action {
+            Action::Exit => "walked off the map".to_string(),
+            _ if self.frame == self.last_frame() => "stuck in a loop".to_string(),
+            _ => format!("at {} facing {}", frame.pos, frame.direction),
+        };
+        format!(
+            "Step {}/{}  {}  {} steps/s  {}  guard {}",
+            frame.step,
+            self.last_frame(),
+            if self.playing { "playing" } else { "paused" },
+            self.speed,
+            if self.view.is_some() {
+                "panned"
+            } else {
+                "following"
+            },
+            state
+        )
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let corner = self.corner();
+        let guard = &self.trace.frames[self.frame];
+        let clear = terminal::Clear(terminal::ClearType::UntilNewLine);
+        queue!(
+            out,
+            cursor::MoveTo(0, 0),
+            Print(self.status().bold()),
+            clear
+        )?;
+        for row in 0..self.size.y {
+            queue!(out, cursor::MoveTo(0, row as u16 + 1))?;
+            for column in 0..self.size.x {
+                let point = corner + Point::new(column, row);
+                let Some(&obstacle) = self.trace.obstacles.get(point) else {
+                    queue!(out, Print(' '))?;
+                    continue;
+                };
+                let cell = if point == guard.pos {
+                    guard.direction.to_char().green().bold()
+                } else if obstacle {
+                    '#'.red()
+                } else if self.first_visits[point] <= self.frame {
+                    '.'.yellow()
+                } else {
+                    '.'.blue().dim()
+                };
+                queue!(out, Print(cell))?;
+            }
+        }
+        let help_row = self.size.y as u16 + 1;
+        queue!(out, cursor::MoveTo(0, help_row), Print(HELP.dim()), clear)?;
+        out.flush()
+    }
+}
+
+// Plays `trace` back in the terminal until the viewer quits.
+pub fn run(trace: &Trace) -> io::Result<()> {
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = play(&mut Replay::new(trace), &mut out);
+    execute!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn play(replay: &mut Replay, out: &mut impl Write) -> io::Result<()> {
+    let mut last = Instant::now();
+    loop {
+        let (width, height) = terminal::size()?;
+        replay.resize(width, height);
+        replay.draw(out)?;
+        if event::poll(Duration::from_millis(30))? {
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.kind == KeyEventKind::Press && (ctrl_c || !replay.handle(key.code)) {
+                    return Ok(());
+                }
+            }
+        }
+        replay.advance(last.elapsed());
+        last = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LabMap;
+
+    #[test]
+    fn test_controls() {
+        let lab_map = LabMap::parse(include_str!("../example_1.txt")).unwrap();
+        let trace = Trace::record(&lab_map);
+        let mut replay = Replay::new(&trace);
+        assert_eq!(replay.first_visits[Point::new(4, 6)], 0);
+        assert_eq!(replay.first_visits[Point::new(4, 1)], 5);
+
+        replay.advance(Duration::from_millis(275));
+        assert_eq!(replay.frame, 5);
+        replay.handle(KeyCode::Char(' '));
+        replay.advance(Duration::from_secs(1));
+        assert_eq!(replay.frame, 5);
+        replay.handle(KeyCode::Left);
+        assert_eq!(replay.frame, 4);
+        replay.handle(KeyCode::End);
+        assert_eq!(replay.frame, trace.frames.len() - 1);
+        replay.handle(KeyCode::Right);
+        assert_eq!(replay.frame, trace.frames.len() - 1);
+
+        replay.handle(KeyCode::Char('d'));
+        assert!(replay.view.is_some());
+        replay.handle(KeyCode::Char('f'));
+        assert_eq!(replay.view, None);
+        replay.handle(KeyCode::Char('+'));
+        assert_eq!(replay.speed, 40);
+        assert!(!replay.handle(KeyCode::Char('q')));
+    }
+}
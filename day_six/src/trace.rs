@@ -0,0 +1,224 @@
+use crate::{LabMap, Outcome};
+use aoc_common::parse;
+use aoc_common::{input, InputSource, LoadError, ParseError};
+use grid::{Direction, Grid, Point};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Start,
+    Move,
+    Turn,
+    // The step that takes the guard off the map, so its position is outside.
+    Exit,
+}
+
+impl Action {
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Start => "start",
+            Action::Move => "move",
+            Action::Turn => "turn",
+            Action::Exit => "exit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    pub step: usize,
+    pub action: Action,
+    pub pos: Point,
+    pub direction: Direction,
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.step,
+            self.action.name(),
+            self.pos.x,
+            self.pos.y,
+            self.direction
+        )
+    }
+}
+
+// A guard's walk, one frame per step, for saving and replaying. Saved traces
+// are the map with the guard on it, a blank line, then a line per frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub obstacles: Grid<bool>,
+    pub frames: Vec<Frame>,
+}
+
+impl Trace {
+    // Walks a copy of the guard from where it is now.
+    pub fn record(lab_map: &LabMap) -> Trace {
+        let mut walk = lab_map.clone();
+        let mut frames = vec![Frame {
+            step: 0,
+            action: Action::Start,
+            pos: walk.guard_pos,
+            direction: walk.guard_direction,
+        }];
+        let outcome = walk.patrol_with(|lab_map| {
+            let last = frames[frames.len() - 1];
+            frames.push(Frame {
+                step: frames.len(),
+                action: if lab_map.guard_pos == last.pos {
+                    Action::Turn
+                } else {
+                    Action::Move
+                },
+                pos: lab_map.guard_pos,
+                direction: lab_map.guard_direction,
+            });
+        });
+        if outcome == Outcome::Exited {
+            frames.push(Frame {
+                step: frames.len(),
+                action: Action::Exit,
+                pos: walk.guard_pos,
+                direction: walk.guard_direction,
+            });
+        }
+        Trace {
+            obstacles: walk.obstacles,
+            frames,
+        }
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        match self.frames.last() {
+            Some(frame) if frame.action == Action::Exit => Outcome::Exited,
+            _ => Outcome::Looped,
+        }
+    }
+
+    pub fn load(source: impl InputSource) -> Result<Trace, LoadError> {
+        input::load(source, Trace::parse)
+    }
+
+    pub fn parse(input: &str) -> Result<Trace, ParseError> {
+        let (map, frames, map_lines) = parse::split_sections(input)
+            .ok_or_else(|| ParseError::whole_input("a blank line after the map", "none"))?;
+        let lab_map = LabMap::parse(map)?;
+        let frames = parse_frames(frames).map_err(|e| e.offset(map_lines))?;
+        match frames.first() {
+            Some(start) if start.action == Action::Start && start.pos == lab_map.guard_pos => {}
+            Some(frame) => {
+                return Err(ParseError::whole_input(
+                    "a start frame on the guard",
+                    frame.to_string(),
+                ))
+            }
+            None => return Err(ParseError::whole_input("a start frame", "none")),
+        }
+        Ok(Trace {
+            obstacles: lab_map.obstacles,
+            frames,
+        })
+    }
+}
+
+fn parse_frames(input: &str) -> Result<Vec<Frame>, ParseError> {
+    let mut frames = Vec::new();
+    for line in parse::lines(input) {
+        let mut parts = line.text.split(' ');
+        let mut next = |expected: &str| match parts.next() {
+            Some(part) if !part.is_empty() => Ok(part),
+            _ => Err(line.error(&line.text[line.text.len()..], expected)),
+        };
+        let step = next("a step number")?;
+        if line.parse::<usize>(step, "a step number")? != frames.len() {
+            return Err(line.error(step, format!("step {}", frames.len())));
+        }
+        let action = next("an action")?;
+        let action = match action {
+            "start" => Action::Start,
+            "move" => Action::Move,
+            "turn" => Action::Turn,
+            "exit" => Action::Exit,
+            _ => return Err(line.error(action, "start, move, turn or exit")),
+        };
+        let x = next("a column")?;
+        let y = next("a row")?;
+        let direction = next("a direction")?;
+        frames.push(Frame {
+            step: frames.len(),
+            action,
+            pos: Point::new(line.parse(x, "a column")?, line.parse(y, "a row")?),
+            direction: line.parse(direction, "'^', '>', 'v' or '<'")?,
+        });
+        if let Some(extra) = parts.next() {
+            return Err(line.error(extra, "end of line"));
+        }
+    }
+    Ok(frames)
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let start = self.frames.first().map(|frame| frame.pos);
+        let map = self.obstacles.render(|point, &obstacle| match obstacle {
+            true => '#',
+            false if Some(point) == start => '^',
+            false => '.',
+        });
+        writeln!(f, "{}", map)?;
+        for frame in &self.frames {
+            writeln!(f, "{}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_reload() {
+        let lab_map = LabMap::parse(include_str!("../example_1.txt")).unwrap();
+        let trace = Trace::record(&lab_map);
+        assert_eq!(trace.outcome(), Outcome::Exited);
+        assert_eq!(trace.frames[0].pos, Point::new(4, 6));
+        assert_eq!(trace.frames[6].to_string(), "6 turn 4 1 >");
+        assert_eq!(trace.frames.last().unwrap().pos, Point::new(7, 10));
+
+        let mut walk = lab_map.clone();
+        walk.patrol();
+        let moves = trace
+            .frames
+            .iter()
+            .filter(|frame| matches!(frame.action, Action::Move | Action::Exit))
+            .count();
+        assert_eq!(moves, walk.steps);
+
+        assert_eq!(Trace::parse(&trace.to_string()).unwrap(), trace);
+        let crlf = trace.to_string().replace('\n', "\r\n");
+        assert_eq!(Trace::parse(&crlf).unwrap(), trace);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let map = "#..\n.^.\n...\n\n";
+        assert!(Trace::parse(&format!("{}0 start 1 1 ^\n1 move 1 0 ^\n", map)).is_ok());
+        assert_eq!(
+            Trace::parse(&format!("{}0 start 1 1 ^\n2 move 1 0 ^\n", map)).unwrap_err(),
+            ParseError::new(6, 1, "step 1", "\"2\"")
+        );
+        assert_eq!(
+            Trace::parse(&format!("{}0 start 1 1 ^\n1 jump 1 0 ^\n", map)).unwrap_err(),
+            ParseError::new(6, 3, "start, move, turn or exit", "\"jump\"")
+        );
+        assert_eq!(
+            Trace::parse(&format!("{}0 start 1 1\n", map)).unwrap_err(),
+            ParseError::new(5, 12, "a direction", "end of line")
+        );
+        assert!(Trace::parse(&format!("{}0 start 2 2 ^\n", map)).is_err());
+    }
+}
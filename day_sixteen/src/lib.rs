@@ -0,0 +1,286 @@
+use aoc_common::{input, InputSource, LoadError, ParseError, Solution, Unsolved};
+use grid::{Direction, Grid, Point};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct Maze {
+    grid: Grid<Cell>,
+    start: Point,
+    end: Point,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Cell {
+    Wall,
+    Path,
+    Start,
+    End,
+}
+
+type State = (Point, Direction);
+
+pub const TURN_COST: i32 = 1000;
+
+// Neighbours are expanded in this order so ties in the open set break the same
+// way they always have.
+const MOVES: [Direction; 4] = [
+    Direction::East,
+    Direction::South,
+    Direction::West,
+    Direction::North,
+];
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+struct Node {
+    position: Point,
+    direction: Direction,
+    f_score: i32,
+    g_score: i32,
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f_score.cmp(&self.f_score)
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Maze {
+    pub fn load(source: impl InputSource) -> Result<Self, LoadError> {
+        input::load(source, Maze::parse)
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let mut start = None;
+        let mut end = None;
+
+        let grid = Grid::try_parse(contents, "'#', '.', 'S' or 'E'", |point, ch| match ch {
+            '#' => Some(Cell::Wall),
+            '.' => Some(Cell::Path),
+            'S' => {
+                start = Some(point);
+                Some(Cell::Start)
+            }
+            'E' => {
+                end = Some(point);
+                Some(Cell::End)
+            }
+            _ => None,
+        })?;
+
+        Ok(Maze {
+            grid,
+            start: start.ok_or_else(|| ParseError::whole_input("a start tile 'S'", "none"))?,
+            end: end.ok_or_else(|| ParseError::whole_input("an end tile 'E'", "none"))?,
+        })
+    }
+
+    fn is_valid_position(&self, pos: Point) -> bool {
+        self.grid.get(pos).is_some_and(|cell| *cell != Cell::Wall)
+    }
+
+    pub fn find_optimal_path(&self, start_direction: Direction) -> Option<(Vec<Point>, i32)> {
+        let mut open_set = BinaryHeap::new();
+        let mut came_from: HashMap<State, State> = HashMap::new();
+        let mut g_scores: HashMap<State, i32> = HashMap::new();
+
+        let start_node = Node {
+            position: self.start,
+            direction: start_direction,
+            f_score: self.start.manhattan(self.end),
+            g_score: 0,
+        };
+
+        g_scores.insert((self.start, start_direction), 0);
+        open_set.push(start_node);
+
+        while let Some(current) = open_set.pop() {
+            if current.position == self.end {
+                return Some((
+                    self.reconstruct_path(&came_from, (current.position, current.direction)),
+                    current.g_score,
+                ));
+            }
+
+            for new_direction in MOVES {
+                let next_pos = current.position + new_direction.delta();
+
+                if !self.is_valid_position(next_pos) {
+                    continue;
+                }
+
+                let turn_cost = current.direction.rotation_cost(new_direction, TURN_COST);
+                let movement_cost = 1;
+                let total_move_cost = turn_cost + movement_cost;
+
+                let tentative_g_score =
+                    g_scores[&(current.position, current.direction)] + total_move_cost;
+
+                if !g_scores.contains_key(&(next_pos, new_direction))
+                    || tentative_g_score < g_scores[&(next_pos, new_direction)]
+                {
+                    came_from.insert(
+                        (next_pos, new_direction),
+                        (current.position, current.direction),
+                    );
+                    g_scores.insert((next_pos, new_direction), tentative_g_score);
+
+                    open_set.push(Node {
+                        position: next_pos,
+                        direction: new_direction,
+                        g_score: tentative_g_score,
+                        f_score: tentative_g_score + next_pos.manhattan(self.end),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn find_all_optimal_paths(&self, start_direction: Direction) -> Vec<Vec<Point>> {
+        let mut open_set = BinaryHeap::new();
+        let mut came_from: HashMap<State, State> = HashMap::new();
+        let mut g_scores: HashMap<State, i32> = HashMap::new();
+        let mut optimal_paths = Vec::new();
+        let mut min_cost = i32::MAX;
+
+        let start_node = Node {
+            position: self.start,
+            direction: start_direction,
+            f_score: self.start.manhattan(self.end),
+            g_score: 0,
+        };
+
+        g_scores.insert((self.start, start_direction), 0);
+        open_set.push(start_node);
+
+        while let Some(current) = open_set.pop() {
+            // If we've found a path and this one is more expensive, we can stop
+            if !optimal_paths.is_empty() && current.g_score > min_cost {
+                break;
+            }
+
+            if current.position == self.end {
+                let path = self.reconstruct_path(&came_from, (current.position, current.direction));
+                if optimal_paths.is_empty() || current.g_score == min_cost {
+                    min_cost = current.g_score;
+                    optimal_paths.push(path);
+                }
+                continue; // Continue searching for other paths of same cost
+            }
+
+            for new_direction in MOVES {
+                let next_pos = current.position + new_direction.delta();
+
+                if !self.is_valid_position(next_pos) {
+                    continue;
+                }
+
+                let turn_cost = current.direction.rotation_cost(new_direction, TURN_COST);
+                let movement_cost = 1;
+                let total_move_cost = turn_cost + movement_cost;
+
+                let tentative_g_score =
+                    g_scores[&(current.position, current.direction)] + total_move_cost;
+
+                if !g_scores.contains_key(&(next_pos, new_direction))
+                    || tentative_g_score < g_scores[&(next_pos, new_direction)]
+                {
+                    came_from.insert(
+                        (next_pos, new_direction),
+                        (current.position, current.direction),
+                    );
+                    g_scores.insert((next_pos, new_direction), tentative_g_score);
+
+                    open_set.push(Node {
+                        position: next_pos,
+                        direction: new_direction,
+                        g_score: tentative_g_score,
+                        f_score: tentative_g_score + next_pos.manhattan(self.end),
+                    });
+                }
+            }
+        }
+
+        optimal_paths
+    }
+
+    fn reconstruct_path(
+        &self,
+        came_from: &HashMap<State, State>,
+        mut current: State,
+    ) -> Vec<Point> {
+        let mut path = vec![current.0];
+        while let Some(&prev) = came_from.get(&current) {
+            path.push(prev.0);
+            current = prev;
+        }
+        path.reverse();
+        path
+    }
+
+    pub fn visualize_path(&self, path: &[Point]) -> String {
+        let path_set: HashSet<_> = path.iter().collect();
+
+        self.grid.render(|point, cell| {
+            if path_set.contains(&point) {
+                'O'
+            } else {
+                match cell {
+                    Cell::Wall => '#',
+                    Cell::Path => '.',
+                    Cell::Start => 'S',
+                    Cell::End => 'E',
+                }
+            }
+        })
+    }
+
+    pub fn count_adjacent_tiles(&self, path: &[Point]) -> usize {
+        let path_set: HashSet<_> = path.iter().cloned().collect();
+        let mut adjacent_set = HashSet::new();
+        for &point in path {
+            for pos in self.grid.neighbours(point) {
+                if self.grid[pos] != Cell::Wall && !path_set.contains(&pos) {
+                    adjacent_set.insert(pos);
+                }
+            }
+        }
+        adjacent_set.len()
+    }
+}
+
+pub struct DaySixteen;
+
+impl Solution for DaySixteen {
+    type Input = Maze;
+    type PartOne = Option<i32>;
+    type PartTwo = Unsolved;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Maze::parse(input)
+    }
+
+    fn part_one(maze: &Self::Input) -> Self::PartOne {
+        let initial_direction = Direction::East;
+        Direction::ALL
+            .into_iter()
+            .filter_map(|try_direction| {
+                maze.find_optimal_path(try_direction).map(|(_, cost)| {
+                    initial_direction.rotation_cost(try_direction, TURN_COST) + cost
+                })
+            })
+            .min()
+    }
+
+    fn part_two(_: &Self::Input) -> Self::PartTwo {
+        Unsolved
+    }
+}
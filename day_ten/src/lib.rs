@@ -0,0 +1,217 @@
+use aoc_common::{input, InputSource, LoadError, ParseError, Solution};
+use grid::{Grid, Point};
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+pub struct TopoMap {
+    pub trailheads: Vec<Point>,
+    grid: Grid<u8>,
+}
+
+impl TopoMap {
+    fn from_lines<S: AsRef<str>>(
+        lines: impl IntoIterator<Item = S>,
+    ) -> Result<TopoMap, ParseError> {
+        let mut trailheads = Vec::new();
+        let grid = Grid::try_parse_lines(lines, "a height from 0 to 9", |point, c| {
+            if c == '0' {
+                trailheads.push(point);
+            }
+            c.to_digit(10).map(|height| height as u8)
+        })?;
+        Ok(TopoMap { trailheads, grid })
+    }
+    pub fn new_from_string(str: Vec<String>) -> Result<TopoMap, ParseError> {
+        TopoMap::from_lines(str)
+    }
+    pub fn load(source: impl InputSource) -> Result<TopoMap, LoadError> {
+        input::load(source, |input| TopoMap::from_lines(input.lines()))
+    }
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+    fn get_value(&self, point: Point) -> Option<u8> {
+        self.grid.get(point).copied()
+    }
+    fn find_paths_from_start(&self, start: Point, valid_paths: &mut Vec<Vec<Point>>) {
+        let mut queue = VecDeque::new();
+        queue.push_back((start, vec![start]));
+        let mut visited = HashSet::new();
+        visited.insert(start);
+
+        while let Some((current_pos, current_path)) = queue.pop_front() {
+            let current_value = self.get_value(current_pos).unwrap();
+
+            if current_value == 9 {
+                valid_paths.push(current_path);
+                continue;
+            }
+
+            for next_pos in current_pos.neighbours() {
+                if visited.contains(&next_pos) {
+                    continue;
+                }
+
+                if let Some(next_value) = self.get_value(next_pos) {
+                    if next_value == current_value + 1 {
+                        visited.insert(next_pos);
+                        let mut new_path = current_path.clone();
+                        new_path.push(next_pos);
+                        queue.push_back((next_pos, new_path));
+                    }
+                }
+            }
+        }
+    }
+    fn find_paths_from_start_all(&self, start: Point, valid_paths: &mut Vec<Vec<Point>>) {
+        let mut queue = vec![(start, vec![start])];
+
+        while let Some((current_pos, current_path)) = queue.pop() {
+            let current_value = self.get_value(current_pos).unwrap();
+
+            if current_value == 9 {
+                valid_paths.push(current_path);
+                continue;
+            }
+
+            for next_pos in current_pos.neighbours() {
+                if let Some(next_value) = self.get_value(next_pos) {
+                    if next_value == current_value + 1 {
+                        let mut new_path = current_path.clone();
+                        new_path.push(next_pos);
+                        queue.push((next_pos, new_path));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn calculate_scores(&self) -> Vec<(Point, usize)> {
+        self.trailheads
+            .iter()
+            .map(|&trailhead| {
+                let mut paths = Vec::new();
+                self.find_paths_from_start(trailhead, &mut paths);
+                (trailhead, paths.len())
+            })
+            .collect()
+    }
+    pub fn calculate_all_scores(&self) -> Vec<(Point, usize)> {
+        self.trailheads
+            .iter()
+            .map(|&trailhead| {
+                let mut paths = Vec::new();
+                self.find_paths_from_start_all(trailhead, &mut paths);
+                (trailhead, paths.len())
+            })
+            .collect()
+    }
+}
+
+pub struct DayTen;
+
+impl Solution for DayTen {
+    type Input = TopoMap;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        TopoMap::from_lines(input.lines())
+    }
+
+    fn part_one(map: &Self::Input) -> Self::PartOne {
+        map.calculate_scores().iter().map(|(_, score)| score).sum()
+    }
+
+    fn part_two(map: &Self::Input) -> Self::PartTwo {
+        map.calculate_all_scores()
+            .iter()
+            .map(|(_, score)| score)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_map() -> TopoMap {
+        let input = vec![
+            String::from("89010123"),
+            String::from("78121874"),
+            String::from("87430965"),
+            String::from("96549874"),
+            String::from("45678903"),
+            String::from("32019012"),
+            String::from("01329801"),
+            String::from("10456732"),
+        ];
+        TopoMap::new_from_string(input).unwrap()
+    }
+
+    #[test]
+    fn test_map_creation() {
+        let map = create_test_map();
+        assert_eq!(map.width(), 8);
+        assert_eq!(map.height(), 8);
+        assert_eq!(map.trailheads.len(), 9);
+    }
+
+    #[test]
+    fn test_invalid_height() {
+        let input = vec![String::from("0123"), String::from("45.7")];
+        let error = TopoMap::new_from_string(input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "input:2:3: expected a height from 0 to 9, found \".\""
+        );
+    }
+
+    #[test]
+    fn test_get_value() {
+        let map = create_test_map();
+        assert_eq!(map.get_value(Point::new(0, 0)), Some(8));
+        assert_eq!(map.get_value(Point::new(2, 1)), Some(1));
+        assert_eq!(map.get_value(Point::new(-1, 0)), None);
+        assert_eq!(map.get_value(Point::new(8, 0)), None);
+    }
+
+    #[test]
+    fn test_single_path() {
+        let map = create_test_map();
+        let mut paths = Vec::new();
+        map.find_paths_from_start(Point::new(3, 0), &mut paths);
+        assert!(!paths.is_empty());
+    }
+
+    #[test]
+    fn test_trailhead_scores() {
+        let map = create_test_map();
+        let scores = map.calculate_scores();
+        assert_eq!(scores.len(), map.trailheads.len());
+
+        let expected_scores = vec![5, 6, 5, 3, 1, 3, 5, 3, 5];
+        let actual_scores: Vec<usize> = scores.iter().map(|(_, score)| *score).collect();
+
+        assert_eq!(actual_scores, expected_scores);
+
+        let all_scores = map.calculate_all_scores();
+        let expected_all_scores = vec![20, 24, 10, 4, 1, 4, 5, 8, 5];
+        let actual_all_scores: Vec<usize> = all_scores.iter().map(|(_, score)| *score).collect();
+        assert_eq!(actual_all_scores, expected_all_scores);
+    }
+
+    #[test]
+    fn test_total_score() {
+        let map = create_test_map();
+        let scores = map.calculate_scores();
+        let all_scores = map.calculate_all_scores();
+        let total: usize = scores.iter().map(|(_, score)| score).sum();
+        let all_total: usize = all_scores.iter().map(|(_, score)| score).sum();
+        assert!(total == 36);
+        assert!(all_total == 81);
+    }
+}
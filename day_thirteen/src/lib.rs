@@ -0,0 +1,184 @@
+use aoc_common::parse::Line;
+use aoc_common::{input, parse, InputSource, LoadError, ParseError, Solution};
+
+const A_TOKENS: usize = 3;
+const B_TOKENS: usize = 1;
+pub const BIG_OFFSET: usize = 10000000000000;
+
+pub struct ClawGame {
+    prize_location: (usize, usize),
+    a_input: (usize, usize),
+    b_input: (usize, usize),
+}
+
+pub fn solve_games(games: &[ClawGame], offset: i64) -> (i64, i64) {
+    // Part A
+    let sum_a: i64 = games
+        .iter()
+        .map(|s| {
+            let mut min_cost = i64::MAX;
+            for i in 1..=100 {
+                for j in 1..=100 {
+                    if s.a_input.0 as i64 * i + s.b_input.0 as i64 * j == s.prize_location.0 as i64
+                        && s.a_input.1 as i64 * i + s.b_input.1 as i64 * j
+                            == s.prize_location.1 as i64
+                    {
+                        let cost = A_TOKENS as i64 * i + B_TOKENS as i64 * j;
+                        if cost < min_cost {
+                            min_cost = cost;
+                        }
+                        break;
+                    }
+                }
+            }
+            if min_cost != i64::MAX {
+                min_cost
+            } else {
+                0
+            }
+        })
+        .sum();
+
+    // Part B
+    let sum_b: i64 = games
+        .iter()
+        .map(|s| {
+            let tx = s.prize_location.0 as i64 + offset;
+            let ty = s.prize_location.1 as i64 + offset;
+            let ax = s.a_input.0 as i64;
+            let ay = s.a_input.1 as i64;
+            let bx = s.b_input.0 as i64;
+            let by = s.b_input.1 as i64;
+
+            if tx % gcd(ax, bx) != 0 || ty % gcd(ay, by) != 0 {
+                return 0;
+            }
+
+            let denom = by * ax - ay * bx;
+            if denom == 0 {
+                return 0;
+            }
+
+            let a = tx * by - ty * bx;
+            if a % denom != 0 {
+                return 0;
+            }
+            let a = a / denom;
+
+            let b = ty * ax - tx * ay;
+            if b % denom != 0 {
+                return 0;
+            }
+            let b = b / denom;
+
+            if a < 0 || b < 0 {
+                return 0;
+            }
+
+            A_TOKENS as i64 * a + B_TOKENS as i64 * b
+        })
+        .sum();
+
+    (sum_a, sum_b)
+}
+
+fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while a != b {
+        if a > b {
+            a -= b;
+        } else {
+            b -= a;
+        }
+    }
+    a
+}
+
+pub fn load_games(source: impl InputSource) -> Result<Vec<ClawGame>, LoadError> {
+    input::load(source, parse_games)
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<ClawGame>, ParseError> {
+    let mut games = Vec::new();
+    let mut current_game = None;
+
+    for line in parse::lines(input) {
+        match (line.number - 1) % 4 {
+            0 => {
+                // Button A line
+                let coords = parse_button_coords(&line, "Button A: ")?;
+                current_game = Some(ClawGame {
+                    prize_location: (0, 0),
+                    a_input: coords,
+                    b_input: (0, 0),
+                });
+            }
+            1 => {
+                // Button B line
+                if let Some(game) = &mut current_game {
+                    game.b_input = parse_button_coords(&line, "Button B: ")?;
+                }
+            }
+            2 => {
+                // Prize line
+                if let Some(mut game) = current_game.take() {
+                    game.prize_location = parse_prize_coords(&line)?;
+                    games.push(game);
+                }
+            }
+            3 => {
+                // Empty line
+                if !line.text.is_empty() {
+                    return Err(line.error(line.text, "a blank line"));
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    if current_game.is_some() {
+        return Err(ParseError::whole_input("a prize line", "end of input"));
+    }
+
+    Ok(games)
+}
+
+fn parse_coords(
+    line: &Line,
+    prefix: &str,
+    x_prefix: &str,
+    y_prefix: &str,
+) -> Result<(usize, usize), ParseError> {
+    let coords = line.strip_prefix(line.text, prefix)?;
+    let (x, y) = line.split_once(coords, ", ")?;
+    let x = line.parse(line.strip_prefix(x, x_prefix)?, "a number")?;
+    let y = line.parse(line.strip_prefix(y, y_prefix)?, "a number")?;
+    Ok((x, y))
+}
+
+fn parse_button_coords(line: &Line, prefix: &str) -> Result<(usize, usize), ParseError> {
+    parse_coords(line, prefix, "X+", "Y+")
+}
+
+fn parse_prize_coords(line: &Line) -> Result<(usize, usize), ParseError> {
+    parse_coords(line, "Prize: ", "X=", "Y=")
+}
+
+pub struct DayThirteen;
+
+impl Solution for DayThirteen {
+    type Input = Vec<ClawGame>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_games(input)
+    }
+
+    fn part_one(games: &Self::Input) -> Self::PartOne {
+        solve_games(games, BIG_OFFSET as i64).0
+    }
+
+    fn part_two(games: &Self::Input) -> Self::PartTwo {
+        solve_games(games, BIG_OFFSET as i64).1
+    }
+}
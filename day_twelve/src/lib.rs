@@ -0,0 +1,203 @@
+use aoc_common::{input, InputSource, LoadError, ParseError, Solution};
+use grid::{Grid, Point};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct Map {
+    plots: Grid<char>,
+    pub regions: HashMap<char, Vec<Vec<Point>>>,
+}
+
+impl Map {
+    pub fn width(&self) -> usize {
+        self.plots.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.plots.height()
+    }
+
+    pub fn detect_regions(&mut self) {
+        let mut visited = Grid::new(self.width(), self.height(), false);
+        self.regions.clear();
+        for start in self.plots.points() {
+            if !visited[start] {
+                let c = self.plots[start];
+                let mut region = Vec::new();
+                let mut queue = vec![start];
+
+                while let Some(current) = queue.pop() {
+                    if visited[current] || self.plots[current] != c {
+                        continue;
+                    }
+                    visited[current] = true;
+                    region.push(current);
+                    queue.extend(self.plots.neighbours(current));
+                }
+                self.regions.entry(c).or_default().push(region);
+            }
+        }
+    }
+
+    pub fn calculate_price(&self) -> usize {
+        let mut total = 0;
+        for regions in self.regions.values() {
+            for region in regions {
+                let area = region.len();
+                let perimeter = self.calculate_perimeter(region);
+                total += area * perimeter;
+            }
+        }
+        total
+    }
+
+    fn calculate_perimeter(&self, region: &[Point]) -> usize {
+        let mut perimeter = 0;
+        for &point in region {
+            let current_char = self.plots[point];
+            for neighbour in point.neighbours() {
+                if self.plots.get(neighbour) != Some(&current_char) {
+                    perimeter += 1;
+                }
+            }
+        }
+
+        perimeter
+    }
+
+    pub fn calculate_sides(&self) -> usize {
+        let mut total_price = 0;
+        for regions in self.regions.values() {
+            for region in regions {
+                let num_cells = region.len();
+                let num_sides = self.count_region_sides(region);
+                total_price += num_cells * num_sides;
+            }
+        }
+        total_price
+    }
+
+    fn count_region_sides(&self, region: &[Point]) -> usize {
+        let region_coords: HashSet<_> = region.iter().cloned().collect();
+        let mut sides = 0;
+
+        let is_in_region = |x: i32, y: i32| -> bool { region_coords.contains(&Point::new(x, y)) };
+
+        for &Point { x, y } in region {
+            for Point { x: dx, y: dy } in Point::ORTHOGONAL {
+                let nx = x + dx;
+                let ny = y + dy;
+
+                if !is_in_region(nx, ny) {
+                    let mut is_new_side = true;
+
+                    if dx == 0 {
+                        // Vertical edge
+                        if is_in_region(x - 1, y) && !is_in_region(x - 1, ny) {
+                            is_new_side = false;
+                        }
+                    } else {
+                        // Horizontal edge
+                        if is_in_region(x, y - 1) && !is_in_region(nx, y - 1) {
+                            is_new_side = false;
+                        }
+                    }
+
+                    if is_new_side {
+                        sides += 1;
+                    }
+                }
+            }
+        }
+        sides
+    }
+}
+
+pub fn parse_map(input: &str) -> Result<Map, ParseError> {
+    let mut regions = HashMap::new();
+    let plots = Grid::try_parse(input, "a plant type (A-Z)", |_, c| {
+        regions.entry(c).or_insert(Vec::new());
+        c.is_ascii_uppercase().then_some(c)
+    })?;
+    Ok(Map { plots, regions })
+}
+
+pub fn load_map(source: impl InputSource) -> Result<Map, LoadError> {
+    input::load(source, parse_map)
+}
+
+pub struct DayTwelve;
+
+impl Solution for DayTwelve {
+    type Input = Map;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut map = parse_map(input)?;
+        map.detect_regions();
+        Ok(map)
+    }
+
+    fn part_one(map: &Self::Input) -> Self::PartOne {
+        map.calculate_price()
+    }
+
+    fn part_two(map: &Self::Input) -> Self::PartTwo {
+        map.calculate_sides()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_map_with_ox_pattern() {
+        let input = vec![
+            "OOOOO".to_string(),
+            "OXOXO".to_string(),
+            "OOOOO".to_string(),
+            "OXOXO".to_string(),
+            "OOOOO".to_string(),
+        ];
+
+        let mut map = load_map(&input).unwrap();
+
+        assert_eq!(map.width(), 5);
+        assert_eq!(map.height(), 5);
+        map.detect_regions();
+        assert_eq!(map.regions.len(), 2);
+        assert_eq!(map.regions.get(&'O').unwrap().len(), 1);
+        assert_eq!(map.regions.get(&'X').unwrap().len(), 4);
+        let total = map.calculate_price();
+        assert_eq!(total, 772);
+    }
+
+    #[test]
+    fn test_load_map_with_complex_pattern() {
+        let input = vec![
+            "RRRRIICCFF".to_string(),
+            "RRRRIICCCF".to_string(),
+            "VVRRRCCFFF".to_string(),
+            "VVRCCCJFFF".to_string(),
+            "VVVVCJJCFE".to_string(),
+            "VVIVCCJJEE".to_string(),
+            "VVIIICJJEE".to_string(),
+            "MIIIIIJJEE".to_string(),
+            "MIIISIJEEE".to_string(),
+            "MMMISSJEEE".to_string(),
+        ];
+
+        let mut map = load_map(&input).unwrap();
+
+        // Test dimensions
+        assert_eq!(map.width(), 10);
+        assert_eq!(map.height(), 10);
+        map.detect_regions();
+        let total = map.calculate_price();
+        assert_eq!(total, 1930);
+        let total_sides = map.calculate_sides();
+        assert_eq!(total_sides, 1206);
+    }
+}
@@ -0,0 +1,287 @@
+pub mod policy;
+pub mod verdict;
+
+use aoc_common::{input, parse, InputSource, LoadError, ParseError, Solution};
+use policy::{Direction, SafetyPolicy};
+
+pub fn load_input(source: impl InputSource) -> Result<Vec<Vec<i32>>, LoadError> {
+    input::load(source, parse_reports)
+}
+
+pub fn parse_reports(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    parse::lines(input)
+        .map(|line| {
+            line.text
+                .split_whitespace()
+                .map(|n| line.parse(n, "a level"))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn calculate_safety_strict(report: &[i32], policy: &SafetyPolicy) -> bool {
+    policy.directions().iter().any(|&direction| {
+        report
+            .windows(2)
+            .all(|pair| policy.allows_step(pair[0], pair[1], direction))
+    })
+}
+
+pub fn check_safety_ish(report: &[i32], policy: &SafetyPolicy) -> bool {
+    removals_within(report, 1, policy).is_some()
+}
+
+// The fewest levels to remove to make the report safe, as indices in
+// ascending order, or None if it takes more than `max`. A report with `max`
+// of 0 is strictly safe, and with 1 it is safe-ish.
+//
+// The levels that are kept form a chain of safe steps, so this finds, for each
+// level, the cheapest chain ending there. Only the `max + 1` levels before it
+// can precede it in a chain, since everything in between is removed, which
+// keeps this O(n * max).
+pub fn removals_within(report: &[i32], max: usize, policy: &SafetyPolicy) -> Option<Vec<usize>> {
+    policy
+        .directions()
+        .iter()
+        .filter_map(|&direction| removals_in_direction(report, max, policy, direction))
+        .min_by_key(Vec::len)
+}
+
+fn removals_in_direction(
+    report: &[i32],
+    max: usize,
+    policy: &SafetyPolicy,
+    direction: Direction,
+) -> Option<Vec<usize>> {
+    let n = report.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+    // For each level, the fewest removals before it with it kept, and the
+    // level kept before it.
+    let mut cost: Vec<Option<usize>> = vec![None; n];
+    let mut previous: Vec<Option<usize>> = vec![None; n];
+    for i in 0..n {
+        if i <= max {
+            cost[i] = Some(i);
+        }
+        for j in i.saturating_sub(max + 1)..i {
+            let Some(before) = cost[j] else { continue };
+            let total = before + (i - j - 1);
+            if total <= max
+                && cost[i].is_none_or(|best| total < best)
+                && policy.allows_step(report[j], report[i], direction)
+            {
+                cost[i] = Some(total);
+                previous[i] = Some(j);
+            }
+        }
+    }
+
+    let (mut last, _) = (n.saturating_sub(max + 1)..n)
+        .filter_map(|i| cost[i].map(|c| (i, c + (n - 1 - i))))
+        .filter(|&(_, total)| total <= max)
+        .min_by_key(|&(_, total)| total)?;
+    let mut kept = vec![false; n];
+    loop {
+        kept[last] = true;
+        match previous[last] {
+            Some(j) => last = j,
+            None => break,
+        }
+    }
+    Some((0..n).filter(|&i| !kept[i]).collect())
+}
+
+// The fewest levels to remove to make the report safe, in O(n * k) where k is
+// how many that turns out to be. Removing all but one level always works.
+pub fn min_removals(report: &[i32], policy: &SafetyPolicy) -> Vec<usize> {
+    let mut max = 0;
+    loop {
+        if let Some(removals) = removals_within(report, max, policy) {
+            return removals;
+        }
+        max = (max * 2).max(1);
+    }
+}
+
+pub struct DayTwo;
+
+impl Solution for DayTwo {
+    type Input = Vec<Vec<i32>>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_reports(input)
+    }
+
+    fn part_one(reports: &Self::Input) -> Self::PartOne {
+        let policy = SafetyPolicy::default();
+        reports
+            .iter()
+            .filter(|r| calculate_safety_strict(r, &policy))
+            .count()
+    }
+
+    fn part_two(reports: &Self::Input) -> Self::PartTwo {
+        let policy = SafetyPolicy::default();
+        reports
+            .iter()
+            .filter(|r| check_safety_ish(r, &policy))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::test_util::compare;
+    static TEST_DATA: &[[i32; 5]] = &[
+        [7, 6, 4, 2, 1],
+        [1, 2, 7, 8, 9],
+        [9, 7, 6, 2, 1],
+        [1, 3, 2, 4, 5],
+        [8, 6, 4, 4, 1],
+        [1, 3, 6, 7, 9],
+    ];
+
+    #[test]
+    fn test_safety_strict() {
+        let policy = SafetyPolicy::default();
+        assert!(
+            calculate_safety_strict(&TEST_DATA[0], &policy),
+            "Expected {:?} to be safe",
+            TEST_DATA[0]
+        );
+        assert!(
+            !calculate_safety_strict(&TEST_DATA[1], &policy),
+            "Expected {:?} to be unsafe",
+            TEST_DATA[1]
+        );
+        assert!(
+            !calculate_safety_strict(&TEST_DATA[2], &policy),
+            "Expected {:?} to be unsafe",
+            TEST_DATA[2]
+        );
+        assert!(
+            !calculate_safety_strict(&TEST_DATA[3], &policy),
+            "Expected {:?} to be unsafe",
+            TEST_DATA[3]
+        );
+        assert!(
+            !calculate_safety_strict(&TEST_DATA[4], &policy),
+            "Expected {:?} to be unsafe",
+            TEST_DATA[4]
+        );
+        assert!(
+            calculate_safety_strict(&TEST_DATA[5], &policy),
+            "Expected {:?} to be safe",
+            TEST_DATA[5]
+        );
+    }
+
+    #[test]
+    fn test_safety_ish() {
+        let policy = SafetyPolicy::default();
+        assert!(
+            check_safety_ish(&TEST_DATA[0], &policy),
+            "Expected {:?} to be safe-ish",
+            TEST_DATA[0]
+        );
+        assert!(
+            !check_safety_ish(&TEST_DATA[1], &policy),
+            "Expected {:?} to be unsafe even after removals",
+            TEST_DATA[1]
+        );
+        assert!(
+            !check_safety_ish(&TEST_DATA[2], &policy),
+            "Expected {:?} to be unsafe even after removals",
+            TEST_DATA[2]
+        );
+        assert!(
+            check_safety_ish(&TEST_DATA[3], &policy),
+            "Expected {:?} to be safe-ish",
+            TEST_DATA[3]
+        );
+        assert!(
+            check_safety_ish(&TEST_DATA[4], &policy),
+            "Expected {:?} to be safe-ish",
+            TEST_DATA[4]
+        );
+        assert!(
+            check_safety_ish(&TEST_DATA[5], &policy),
+            "Expected {:?} to be safe-ish",
+            TEST_DATA[5]
+        );
+    }
+
+    #[test]
+    fn test_min_removals() {
+        let policy = SafetyPolicy::default();
+        let needed: Vec<usize> = TEST_DATA
+            .iter()
+            .map(|r| min_removals(r, &policy).len())
+            .collect();
+        assert_eq!(needed, [0, 2, 2, 1, 1, 0]);
+        assert_eq!(min_removals(&[1, 2, 9, 3, 4], &policy), [2]);
+        assert_eq!(min_removals(&[1, 9, 9, 2, 3], &policy), [1, 2]);
+        assert_eq!(removals_within(&[1, 9, 9, 2, 3], 1, &policy), None);
+        assert_eq!(min_removals(&[5, 5, 5, 5], &policy).len(), 3);
+        assert_eq!(min_removals(&[], &policy), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_min_removals_matches_brute_force() {
+        let policy = SafetyPolicy::default();
+        // Every subset of levels kept, smallest number removed first.
+        fn brute_force(report: &[i32], policy: &SafetyPolicy) -> usize {
+            let n = report.len();
+            (0..1u32 << n)
+                .filter(|mask| {
+                    let kept: Vec<i32> = (0..n)
+                        .filter(|i| mask & (1 << i) != 0)
+                        .map(|i| report[i])
+                        .collect();
+                    calculate_safety_strict(&kept, policy)
+                })
+                .map(|mask| n - mask.count_ones() as usize)
+                .min()
+                .unwrap()
+        }
+
+        compare(
+            12345,
+            500,
+            |random| (0..8).map(|_| random.below(10) as i32).collect::<Vec<_>>(),
+            |report| {
+                let removals = min_removals(report, &policy);
+                let kept: Vec<i32> = (0..report.len())
+                    .filter(|i| !removals.contains(i))
+                    .map(|i| report[i])
+                    .collect();
+                assert!(calculate_safety_strict(&kept, &policy), "{:?}", report);
+                removals.len()
+            },
+            |report| brute_force(report, &policy),
+        );
+    }
+
+    #[test]
+    fn test_policies() {
+        let report = [1, 2, 2, 7, 8];
+        let mut policy = SafetyPolicy::default();
+        assert_eq!(min_removals(&report, &policy).len(), 3);
+        policy.allow_plateaus = true;
+        policy.max_step = 5;
+        assert!(calculate_safety_strict(&report, &policy));
+        policy.direction = Direction::Decreasing;
+        assert!(!check_safety_ish(&report, &policy));
+        policy.direction = Direction::Any;
+        assert!(calculate_safety_strict(&[1, 3, 2, 4], &policy));
+
+        let odd_levels = SafetyPolicy::default().with_predicate(|_, b| b % 2 == 1);
+        assert!(calculate_safety_strict(&[1, 3, 5], &odd_levels));
+        assert_eq!(min_removals(&[1, 3, 4, 5], &odd_levels), [2]);
+    }
+}
@@ -1,153 +1,92 @@
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use aoc_common::cli::Args;
+use aoc_common::{unwrap_or_exit, Part};
+use clap::{Parser, ValueEnum};
+use day_two::parse_reports;
+use day_two::policy::{Direction, SafetyPolicy};
+use day_two::verdict::{self, diagnose, Status, Verdict};
+use std::path::PathBuf;
 
-fn main() -> io::Result<()> {
-    let mut safe_count = 0;
-    let mut safe_ish_count = 0;
-    let data = load_input("input.txt")?;
-    println!("Loaded {} reports.", data.len());
-    for report in &data {
-        if calculate_safety_strict(report) {
-            safe_count += 1;
-            safe_ish_count += 1;
-            println!("Vector {:?} is strictly safe", report);
-        } else if check_safety_ish(report) {
-            println!("Vector {:?} is safe-ish", report);
-            safe_ish_count += 1;
-        } else {
-            println!("Vector {:?} is not safe even after removals", report);
-        }
-    }
-    println!("Safe count: {}", safe_count);
-    println!("Safe-ish count: {}", safe_ish_count);
-    Ok(())
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    args: Args,
+    /// TOML file with the safety rules to use instead of the puzzle's
+    #[arg(long)]
+    policy: Option<PathBuf>,
+    /// Smallest step allowed between adjacent levels
+    #[arg(long)]
+    min_step: Option<i32>,
+    /// Largest step allowed between adjacent levels
+    #[arg(long)]
+    max_step: Option<i32>,
+    /// Allow adjacent levels to be equal, or with `=false` forbid it
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    allow_plateaus: Option<bool>,
+    /// Which way the levels have to go
+    #[arg(long, value_enum)]
+    direction: Option<Direction>,
+    /// Print a verdict on every report instead of the answers
+    #[arg(long, value_enum)]
+    export: Option<Format>,
 }
 
-fn load_input(file_path: &str) -> io::Result<Vec<Vec<i32>>> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-
-    let mut data = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        let numbers: Vec<i32> = line
-            .split_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect();
-        data.push(numbers);
-    }
-    Ok(data)
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Csv,
 }
 
-fn calculate_safety_strict(report: &[i32]) -> bool {
-    if report.len() < 2 {
-        return true;
+impl Cli {
+    // The policy file, if any, with the flags given on top.
+    fn policy(&self) -> SafetyPolicy {
+        let mut policy = match &self.policy {
+            Some(path) => unwrap_or_exit(SafetyPolicy::load(path)),
+            None => SafetyPolicy::default(),
+        };
+        policy.min_step = self.min_step.unwrap_or(policy.min_step);
+        policy.max_step = self.max_step.unwrap_or(policy.max_step);
+        policy.allow_plateaus = self.allow_plateaus.unwrap_or(policy.allow_plateaus);
+        policy.direction = self.direction.unwrap_or(policy.direction);
+        unwrap_or_exit(policy.check().map_err(|e| e.in_file("arguments")));
+        policy
     }
-
-    let is_increasing_monotonically = report.windows(2).all(|pair| pair[0] < pair[1]);
-    let is_decreasing_monotonically = report.windows(2).all(|pair| pair[0] > pair[1]);
-    let condition_one = is_increasing_monotonically || is_decreasing_monotonically;
-
-    let condition_two = report.windows(2).all(|pair| {
-        let (a, b) = (pair[0], pair[1]);
-        ((b - a).abs() >= 1) && ((b - a).abs() <= 3)
-    });
-
-    condition_one && condition_two
 }
 
-fn check_safety_ish(report: &[i32]) -> bool {
-    if calculate_safety_strict(report) {
-        return true;
+fn main() {
+    let cli = Cli::parse();
+    let args = &cli.args;
+    let policy = cli.policy();
+    let data = unwrap_or_exit(args.load(&["input.txt"], parse_reports));
+    let verdicts: Vec<Verdict> = data
+        .iter()
+        .map(|report| diagnose(report, &policy))
+        .collect();
+    match cli.export {
+        Some(Format::Json) => return print!("{}", verdict::json(&verdicts)),
+        Some(Format::Csv) => return print!("{}", verdict::csv(&verdicts)),
+        None => {}
     }
 
-    for i in 0..report.len() {
-        let mut mod_report: Vec<i32> = report.to_vec();
-        mod_report.remove(i);
-        if calculate_safety_strict(&mod_report) {
-            return true;
+    if args.verbose > 0 {
+        println!("Loaded {} reports.", data.len());
+        println!("Using {:?}", policy);
+        for verdict in &verdicts {
+            println!("{}", verdict);
         }
     }
-    false
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    static TEST_DATA: &[[i32; 5]] = &[
-        [7, 6, 4, 2, 1],
-        [1, 2, 7, 8, 9],
-        [9, 7, 6, 2, 1],
-        [1, 3, 2, 4, 5],
-        [8, 6, 4, 4, 1],
-        [1, 3, 6, 7, 9],
-    ];
-
-    #[test]
-    fn test_safety_strict() {
-        assert!(
-            calculate_safety_strict(&TEST_DATA[0]),
-            "Expected {:?} to be safe",
-            TEST_DATA[0]
-        );
-        assert!(
-            !calculate_safety_strict(&TEST_DATA[1]),
-            "Expected {:?} to be unsafe",
-            TEST_DATA[1]
-        );
-        assert!(
-            !calculate_safety_strict(&TEST_DATA[2]),
-            "Expected {:?} to be unsafe",
-            TEST_DATA[2]
-        );
-        assert!(
-            !calculate_safety_strict(&TEST_DATA[3]),
-            "Expected {:?} to be unsafe",
-            TEST_DATA[3]
-        );
-        assert!(
-            !calculate_safety_strict(&TEST_DATA[4]),
-            "Expected {:?} to be unsafe",
-            TEST_DATA[4]
-        );
-        assert!(
-            calculate_safety_strict(&TEST_DATA[5]),
-            "Expected {:?} to be safe",
-            TEST_DATA[5]
-        );
+    let count = |statuses: &[Status]| {
+        verdicts
+            .iter()
+            .filter(|v| statuses.contains(&v.status))
+            .count()
+    };
+    if args.wants(Part::One) {
+        println!("Safe count: {}", count(&[Status::Safe]));
     }
-
-    #[test]
-    fn test_safety_ish() {
-        assert!(
-            check_safety_ish(&TEST_DATA[0]),
-            "Expected {:?} to be safe-ish",
-            TEST_DATA[0]
-        );
-        assert!(
-            !check_safety_ish(&TEST_DATA[1]),
-            "Expected {:?} to be unsafe even after removals",
-            TEST_DATA[1]
-        );
-        assert!(
-            !check_safety_ish(&TEST_DATA[2]),
-            "Expected {:?} to be unsafe even after removals",
-            TEST_DATA[2]
-        );
-        assert!(
-            check_safety_ish(&TEST_DATA[3]),
-            "Expected {:?} to be safe-ish",
-            TEST_DATA[3]
-        );
-        assert!(
-            check_safety_ish(&TEST_DATA[4]),
-            "Expected {:?} to be safe-ish",
-            TEST_DATA[4]
-        );
-        assert!(
-            check_safety_ish(&TEST_DATA[5]),
-            "Expected {:?} to be safe-ish",
-            TEST_DATA[5]
+    if args.wants(Part::Two) {
+        println!(
+            "Safe-ish count: {}",
+            count(&[Status::Safe, Status::SafeIsh])
         );
     }
 }
@@ -0,0 +1,210 @@
+use aoc_common::{input, InputSource, LoadError, ParseError};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    // Every step goes the same way, up or down.
+    #[default]
+    Either,
+    Increasing,
+    Decreasing,
+    // Steps may go either way, each judged on its own.
+    Any,
+}
+
+pub type Predicate = Arc<dyn Fn(i32, i32) -> bool + Send + Sync>;
+
+// The rules a report has to follow to be safe. The default is the puzzle's:
+// every step between adjacent levels goes the same way, by 1 to 3.
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SafetyPolicy {
+    // Bounds on the size of a step up or down.
+    pub min_step: i32,
+    pub max_step: i32,
+    // Whether adjacent levels may be equal, whatever `min_step` is.
+    pub allow_plateaus: bool,
+    pub direction: Direction,
+    // A further check on every pair of adjacent levels, which can only be set
+    // from code.
+    #[serde(skip)]
+    pub predicate: Option<Predicate>,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            allow_plateaus: false,
+            direction: Direction::Either,
+            predicate: None,
+        }
+    }
+}
+
+impl fmt::Debug for SafetyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SafetyPolicy")
+            .field("min_step", &self.min_step)
+            .field("max_step", &self.max_step)
+            .field("allow_plateaus", &self.allow_plateaus)
+            .field("direction", &self.direction)
+            .field("predicate", &self.predicate.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+impl SafetyPolicy {
+    // A policy file has any of the fields above, with the rest left at their
+    // defaults:
+    //
+    //     min_step = 1
+    //     max_step = 5
+    //     allow_plateaus = true
+    //     direction = "increasing"
+    pub fn from_toml(text: &str) -> Result<Self, ParseError> {
+        let policy: SafetyPolicy = toml::from_str(text).map_err(|e| {
+            let (line, column) = match e.span() {
+                Some(span) => {
+                    let before = &text[..span.start];
+                    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                    (
+                        before.matches('\n').count() + 1,
+                        span.start - line_start + 1,
+                    )
+                }
+                None => (0, 0),
+            };
+            ParseError::new(line, column, "a safety policy", e.message())
+        })?;
+        policy.check()?;
+        Ok(policy)
+    }
+
+    pub fn load(source: impl InputSource) -> Result<Self, LoadError> {
+        input::load(source, Self::from_toml)
+    }
+
+    pub fn with_predicate(
+        mut self,
+        predicate: impl Fn(i32, i32) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    pub fn check(&self) -> Result<(), ParseError> {
+        if self.min_step < 1 || self.min_step > self.max_step {
+            return Err(ParseError::whole_input(
+                "steps with 1 <= min_step <= max_step",
+                format!("min_step {} and max_step {}", self.min_step, self.max_step),
+            ));
+        }
+        Ok(())
+    }
+
+    // The fixed directions a safe report can take, one pass each when looking
+    // for the levels to remove.
+    pub fn directions(&self) -> &'static [Direction] {
+        match self.direction {
+            Direction::Either => &[Direction::Increasing, Direction::Decreasing],
+            Direction::Increasing => &[Direction::Increasing],
+            Direction::Decreasing => &[Direction::Decreasing],
+            Direction::Any => &[Direction::Any],
+        }
+    }
+
+    // Whether a report heading in `direction` may step from `a` to `b`.
+    pub fn allows_step(&self, a: i32, b: i32, direction: Direction) -> bool {
+        self.violation(a, b, direction).is_none()
+    }
+
+    // The rule a step from `a` to `b` breaks, if any.
+    pub fn violation(&self, a: i32, b: i32, direction: Direction) -> Option<Rule> {
+        let step = b as i64 - a as i64;
+        let heading = match direction {
+            Direction::Increasing => step >= 0,
+            Direction::Decreasing => step <= 0,
+            Direction::Either | Direction::Any => true,
+        };
+        if step == 0 && !self.allow_plateaus {
+            Some(Rule::Plateau)
+        } else if !heading {
+            Some(Rule::DirectionFlip)
+        } else if step != 0 && step.abs() < self.min_step as i64 {
+            Some(Rule::StepTooSmall)
+        } else if step.abs() > self.max_step as i64 {
+            Some(Rule::StepTooLarge)
+        } else if self
+            .predicate
+            .as_ref()
+            .is_some_and(|predicate| !predicate(a, b))
+        {
+            Some(Rule::Predicate)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    DirectionFlip,
+    StepTooSmall,
+    StepTooLarge,
+    Plateau,
+    Predicate,
+}
+
+impl Rule {
+    // For machine-readable output.
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::DirectionFlip => "direction_flip",
+            Rule::StepTooSmall => "step_too_small",
+            Rule::StepTooLarge => "step_too_large",
+            Rule::Plateau => "plateau",
+            Rule::Predicate => "predicate",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::DirectionFlip => write!(f, "changes direction"),
+            Rule::StepTooSmall => write!(f, "is too small"),
+            Rule::StepTooLarge => write!(f, "is too large"),
+            Rule::Plateau => write!(f, "is a plateau"),
+            Rule::Predicate => write!(f, "fails the custom check"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let policy = SafetyPolicy::from_toml("max_step = 5\ndirection = \"increasing\"\n").unwrap();
+        assert_eq!(policy.min_step, 1);
+        assert_eq!(policy.max_step, 5);
+        assert_eq!(policy.direction, Direction::Increasing);
+
+        let error = SafetyPolicy::from_toml("min_step = 1\nmax_step = \"3\"\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 12));
+        let error = SafetyPolicy::from_toml("max_stride = 2\n").unwrap_err();
+        assert_eq!(error.line, 1);
+        let error = SafetyPolicy::from_toml("min_step = 4\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "input: expected steps with 1 <= min_step <= max_step, found min_step 4 and max_step 3"
+        );
+    }
+}
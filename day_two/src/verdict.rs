@@ -0,0 +1,220 @@
+use crate::min_removals;
+use crate::policy::{Direction, Rule, SafetyPolicy};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Safe,
+    // Safe once one level is removed.
+    SafeIsh,
+    Unsafe,
+}
+
+impl Status {
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Safe => "safe",
+            Status::SafeIsh => "safe_ish",
+            Status::Unsafe => "unsafe",
+        }
+    }
+}
+
+// A pair of adjacent levels that breaks the policy, starting at `index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub index: usize,
+    pub levels: (i32, i32),
+    pub rule: Rule,
+}
+
+// Why a report is or isn't safe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    pub levels: Vec<i32>,
+    pub status: Status,
+    // The first pair of levels that breaks the policy, for any report that
+    // isn't strictly safe.
+    pub violation: Option<Violation>,
+    // The fewest levels, by index, whose removal makes the report safe.
+    pub removals: Vec<usize>,
+}
+
+pub fn diagnose(report: &[i32], policy: &SafetyPolicy) -> Verdict {
+    let violation = first_violation(report, policy);
+    let removals = match violation {
+        Some(_) => min_removals(report, policy),
+        None => Vec::new(),
+    };
+    let status = match removals.len() {
+        0 => Status::Safe,
+        1 => Status::SafeIsh,
+        _ => Status::Unsafe,
+    };
+    Verdict {
+        levels: report.to_vec(),
+        status,
+        violation,
+        removals,
+    }
+}
+
+// When the policy lets a report go either way, the first step that isn't level
+// decides which way it's going.
+fn first_violation(report: &[i32], policy: &SafetyPolicy) -> Option<Violation> {
+    let direction = match policy.direction {
+        Direction::Either => match report.windows(2).find(|pair| pair[0] != pair[1]) {
+            Some(pair) if pair[0] > pair[1] => Direction::Decreasing,
+            _ => Direction::Increasing,
+        },
+        direction => direction,
+    };
+    report.windows(2).enumerate().find_map(|(index, pair)| {
+        policy
+            .violation(pair[0], pair[1], direction)
+            .map(|rule| Violation {
+                index,
+                levels: (pair[0], pair[1]),
+                rule,
+            })
+    })
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Vector {:?} is ", self.levels)?;
+        match self.status {
+            Status::Safe => return write!(f, "strictly safe"),
+            Status::SafeIsh => write!(f, "safe-ish")?,
+            Status::Unsafe => write!(f, "not safe even after removals")?,
+        }
+        if let Some(v) = &self.violation {
+            write!(
+                f,
+                ": the step from {} to {} at index {} {}",
+                v.levels.0, v.levels.1, v.index, v.rule
+            )?;
+        }
+        match self.status {
+            Status::SafeIsh => write!(f, ", safe without the level at index {}", self.removals[0]),
+            _ => write!(f, ", it needs the levels at {:?} removed", self.removals),
+        }
+    }
+}
+
+fn join(values: impl IntoIterator<Item = impl ToString>, separator: &str) -> String {
+    values
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+// One JSON object per line, with reports numbered from 1 in input order.
+pub fn json(verdicts: &[Verdict]) -> String {
+    let mut json = String::new();
+    for (i, verdict) in verdicts.iter().enumerate() {
+        let violation = match &verdict.violation {
+            Some(v) => format!(
+                "{{\"index\":{},\"levels\":[{},{}],\"rule\":\"{}\"}}",
+                v.index,
+                v.levels.0,
+                v.levels.1,
+                v.rule.name()
+            ),
+            None => "null".to_string(),
+        };
+        json += &format!(
+            "{{\"report\":{},\"levels\":[{}],\"status\":\"{}\",\"violation\":{},\"removals\":[{}]}}\n",
+            i + 1,
+            join(&verdict.levels, ","),
+            verdict.status.name(),
+            violation,
+            join(&verdict.removals, ",")
+        );
+    }
+    json
+}
+
+// The same as `json`, with lists of levels and removals separated by spaces
+// and the violation's fields left empty for safe reports.
+pub fn csv(verdicts: &[Verdict]) -> String {
+    let mut csv = String::from("report,levels,status,index,first,second,rule,removals\n");
+    for (i, verdict) in verdicts.iter().enumerate() {
+        let violation = match &verdict.violation {
+            Some(v) => format!(
+                "{},{},{},{}",
+                v.index,
+                v.levels.0,
+                v.levels.1,
+                v.rule.name()
+            ),
+            None => ",,,".to_string(),
+        };
+        csv += &format!(
+            "{},{},{},{},{}\n",
+            i + 1,
+            join(&verdict.levels, " "),
+            verdict.status.name(),
+            violation,
+            join(&verdict.removals, " ")
+        );
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose() {
+        let policy = SafetyPolicy::default();
+        let rules: Vec<Option<(usize, Rule)>> = [
+            &[7, 6, 4, 2, 1][..],
+            &[1, 2, 7, 8, 9],
+            &[1, 3, 2, 4, 5],
+            &[8, 6, 4, 4, 1],
+        ]
+        .iter()
+        .map(|report| first_violation(report, &policy).map(|v| (v.index, v.rule)))
+        .collect();
+        assert_eq!(
+            rules,
+            [
+                None,
+                Some((1, Rule::StepTooLarge)),
+                Some((1, Rule::DirectionFlip)),
+                Some((2, Rule::Plateau))
+            ]
+        );
+
+        let verdict = diagnose(&[1, 3, 2, 4, 5], &policy);
+        assert_eq!(verdict.status, Status::SafeIsh);
+        assert_eq!(verdict.removals.len(), 1);
+        assert_eq!(
+            diagnose(&[1, 2, 7, 8, 9], &policy).to_string(),
+            "Vector [1, 2, 7, 8, 9] is not safe even after removals: the step from 2 to 7 at index 1 is too large, it needs the levels at [0, 1] removed"
+        );
+    }
+
+    #[test]
+    fn test_export() {
+        let policy = SafetyPolicy::default();
+        let verdicts = [
+            diagnose(&[1, 2], &policy),
+            diagnose(&[8, 6, 4, 4, 1], &policy),
+        ];
+        assert_eq!(
+            json(&verdicts),
+            "{\"report\":1,\"levels\":[1,2],\"status\":\"safe\",\"violation\":null,\"removals\":[]}\n\
+             {\"report\":2,\"levels\":[8,6,4,4,1],\"status\":\"safe_ish\",\"violation\":{\"index\":2,\"levels\":[4,4],\"rule\":\"plateau\"},\"removals\":[3]}\n"
+        );
+        assert_eq!(
+            csv(&verdicts),
+            "report,levels,status,index,first,second,rule,removals\n\
+             1,1 2,safe,,,,,\n\
+             2,8 6 4 4 1,safe_ish,2,4,4,plateau,3\n"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::Point;
+use std::fmt;
+use std::str::FromStr;
+
+// North is up the screen. Deltas follow the Point convention (x is the column,
+// y is the row growing downwards), so North is (0, -1) and East is (1, 0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    // Clockwise, starting from North.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn rotate(self, quarter_turns: usize) -> Direction {
+        Direction::ALL[(self.index() + quarter_turns) % 4]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(1)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(3)
+    }
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::North => Point::UP,
+            Direction::East => Point::RIGHT,
+            Direction::South => Point::DOWN,
+            Direction::West => Point::LEFT,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+
+    // Fewest 90 degree turns needed to face `other`, turning either way.
+    pub fn quarter_turns(self, other: Direction) -> u32 {
+        let diff = (other.index() + 4 - self.index()) % 4;
+        diff.min(4 - diff) as u32
+    }
+
+    pub fn rotation_cost(self, other: Direction, cost_per_turn: i32) -> i32 {
+        self.quarter_turns(other) as i32 * cost_per_turn
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' => Ok(Direction::North),
+            '>' => Ok(Direction::East),
+            'v' => Ok(Direction::South),
+            '<' => Ok(Direction::West),
+            _ => Err(format!("Invalid direction: {}", c)),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(format!("Invalid direction: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turning() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+        }
+    }
+
+    #[test]
+    fn test_delta_and_parse() {
+        assert_eq!(Direction::North.delta(), Point::new(0, -1));
+        assert_eq!(Direction::East.delta(), Point::new(1, 0));
+        assert_eq!("v".parse::<Direction>(), Ok(Direction::South));
+        assert_eq!(Direction::try_from('<'), Ok(Direction::West));
+        assert!(Direction::try_from('x').is_err());
+        assert!("^^".parse::<Direction>().is_err());
+        for direction in Direction::ALL {
+            assert_eq!(Direction::try_from(direction.to_char()), Ok(direction));
+        }
+    }
+
+    #[test]
+    fn test_rotation_cost() {
+        assert_eq!(Direction::North.quarter_turns(Direction::North), 0);
+        assert_eq!(Direction::North.quarter_turns(Direction::West), 1);
+        assert_eq!(Direction::East.quarter_turns(Direction::West), 2);
+        assert_eq!(Direction::South.rotation_cost(Direction::East, 1000), 1000);
+        assert_eq!(Direction::South.rotation_cost(Direction::North, 1000), 2000);
+    }
+}
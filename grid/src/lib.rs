@@ -0,0 +1,290 @@
+mod direction;
+mod point;
+
+pub use direction::Direction;
+pub use point::Point;
+
+use aoc_common::parse::Line;
+use aoc_common::ParseError;
+
+use std::fmt::{self, Write};
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn parse(input: &str, map: impl FnMut(Point, char) -> T) -> Result<Self, ParseError> {
+        Grid::parse_lines(input.lines(), map)
+    }
+
+    // Every row has to be as wide as the first one, or it's reported as an
+    // error; blank lines are skipped so a trailing newline doesn't add an
+    // empty row.
+    pub fn parse_lines<I, S>(
+        lines: I,
+        mut map: impl FnMut(Point, char) -> T,
+    ) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Grid::try_parse_lines(lines, "", |point, c| Some(map(point, c)))
+    }
+
+    pub fn try_parse(
+        input: &str,
+        expected: &str,
+        map: impl FnMut(Point, char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Grid::try_parse_lines(input.lines(), expected, map)
+    }
+
+    // Like `parse_lines`, but characters `map` rejects are reported as errors
+    // too, with `expected` describing the characters allowed.
+    pub fn try_parse_lines<I, S>(
+        lines: I,
+        expected: &str,
+        mut map: impl FnMut(Point, char) -> Option<T>,
+    ) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut width = 0;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (i, text) in lines.into_iter().enumerate() {
+            let line = Line {
+                number: i + 1,
+                text: text.as_ref(),
+            };
+            if line.text.is_empty() {
+                continue;
+            }
+            let row_width = line.text.chars().count();
+            if height == 0 {
+                width = row_width;
+            }
+            if row_width != width {
+                return Err(ParseError::new(
+                    line.number,
+                    width.min(row_width) + 1,
+                    format!("a row {} wide", width),
+                    format!("{} characters", row_width),
+                ));
+            }
+            for (x, (offset, c)) in line.text.char_indices().enumerate() {
+                let point = Point::new(x as i32, height as i32);
+                match map(point, c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        let part = &line.text[offset..offset + c.len_utf8()];
+                        return Err(line.error(part, expected));
+                    }
+                }
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    pub fn set(&mut self, point: Point, value: T) -> bool {
+        match self.get_mut(point) {
+            Some(cell) => {
+                *cell = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.width * self.height)
+            .map(move |i| Point::new((i % width) as i32, (i / width) as i32))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours().filter(|&p| self.contains(p))
+    }
+
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbours8().filter(|&p| self.contains(p))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Point, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(point, cell)| f(point, cell)).collect(),
+        }
+    }
+
+    pub fn render<D: fmt::Display>(&self, mut f: impl FnMut(Point, &T) -> D) -> String {
+        let mut result = String::new();
+        for (point, cell) in self.iter() {
+            write!(result, "{}", f(point, cell)).unwrap();
+            if point.x as usize == self.width - 1 {
+                result.push('\n');
+            }
+        }
+        result
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{} is outside the grid", point))
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(|_, &c| c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#.#\n..#\n#..\n";
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = Grid::parse(EXAMPLE, |_, c| c == '#').unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert!(grid[Point::new(0, 0)]);
+        assert!(!grid[Point::new(1, 0)]);
+        assert!(grid[Point::new(2, 1)]);
+        assert_eq!(
+            grid.render(|_, &wall| if wall { '#' } else { '.' }),
+            EXAMPLE
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = Grid::new(2, 3, 0);
+        assert!(grid.contains(Point::new(1, 2)));
+        assert!(!grid.contains(Point::new(2, 0)));
+        assert!(!grid.contains(Point::new(0, -1)));
+        assert_eq!(grid.get(Point::new(0, 3)), None);
+        assert!(grid.set(Point::new(1, 1), 5));
+        assert!(!grid.set(Point::new(-1, 1), 5));
+        assert_eq!(grid[Point::new(1, 1)], 5);
+    }
+
+    #[test]
+    fn test_neighbours_stay_in_bounds() {
+        let grid = Grid::parse(EXAMPLE, |_, c| c).unwrap();
+        assert_eq!(grid.neighbours(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_find_and_points() {
+        let grid = Grid::parse("..\n.S\n", |_, c| c).unwrap();
+        assert_eq!(grid.find(|&c| c == 'S'), Some(Point::new(1, 1)));
+        assert_eq!(grid.points().last(), Some(Point::new(1, 1)));
+        assert_eq!(grid.to_string(), "..\n.S\n");
+    }
+
+    #[test]
+    fn test_try_parse_reports_position() {
+        let walls = |_, c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        assert!(Grid::try_parse(EXAMPLE, "'#' or '.'", walls).is_ok());
+        assert_eq!(
+            Grid::try_parse("#.#\n.x#\n", "'#' or '.'", walls),
+            Err(ParseError::new(2, 2, "'#' or '.'", "\"x\""))
+        );
+        assert_eq!(
+            Grid::try_parse("#.#\n.#\n", "'#' or '.'", walls)
+                .unwrap_err()
+                .expected,
+            "a row 3 wide"
+        );
+        assert_eq!(
+            Grid::parse("##\n#\n", |_, c| c).unwrap_err(),
+            ParseError::new(2, 2, "a row 2 wide", "1 characters")
+        );
+    }
+}
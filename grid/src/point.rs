@@ -0,0 +1,136 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// x is the column and y is the row, so y grows downwards the way puzzle input
+// is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const RIGHT: Point = Point::new(1, 0);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+
+    pub const ORTHOGONAL: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+    pub const ADJACENT: [Point; 8] = [
+        Point::new(-1, -1),
+        Point::UP,
+        Point::new(1, -1),
+        Point::RIGHT,
+        Point::new(1, 1),
+        Point::DOWN,
+        Point::new(-1, 1),
+        Point::LEFT,
+    ];
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Point::ORTHOGONAL.into_iter().map(move |delta| self + delta)
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Point::ADJACENT.into_iter().map(move |delta| self + delta)
+    }
+
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as i32, y as i32)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+    fn mul(self, scale: i32) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + Point::RIGHT, Point::new(3, 3));
+        assert_eq!(p - Point::new(1, 1), Point::new(1, 2));
+        assert_eq!(Point::UP * 3, Point::new(0, -3));
+        assert_eq!(-Point::LEFT, Point::RIGHT);
+        assert_eq!(p.manhattan(Point::ORIGIN), 5);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point::new(1, 1);
+        let four: Vec<Point> = p.neighbours().collect();
+        assert_eq!(
+            four,
+            vec![
+                Point::new(1, 0),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(0, 1)
+            ]
+        );
+        assert_eq!(p.neighbours8().count(), 8);
+        assert!(!p.neighbours8().any(|n| n == p));
+    }
+}